@@ -1,9 +1,40 @@
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
 use bevy::prelude::Resource;
-use egui::{Align, Layout, TextStyle, Label};
+use egui::{Align, Align2, Button, Color32, DragValue, Key, Layout, Modifiers, RichText, Stroke, TextStyle, Label};
+use egui::ProgressBar;
 
 use root_path::RootPath;
-use display::Display;
+use sil::SectionKind;
+use display::{Clear, Display, Images, Settings, Theme};
+use project::{Action, Project, WindowSettings};
 use slides::Slides;
+use history::History;
+use keymap::Shortcuts;
+use messages::MessagePanel;
+use stage::StageDisplay;
+
+mod history;
+mod keymap;
+mod messages;
+mod stage;
+
+/// Marks what the audience sees.
+const LIVE_COLOR: Color32 = Color32::from_rgb(220, 40, 40);
+
+/// Actions clearing the output, and what they clear.
+const CLEAR_ACTIONS: [(Action, Clear); 4] = [
+    (Action::ClearText, Clear::Text),
+    (Action::ClearBackground, Clear::Background),
+    (Action::Black, Clear::All),
+    (Action::Logo, Clear::Logo),
+];
+const CLEAR_NAMES: [&str; 4] = ["clear text", "clear background", "black", "logo"];
+
+/// Where exports go, in the root folder.
+const EXPORT_FILE: &str = "export.txt";
 
 #[derive(Resource)]
 pub struct App {
@@ -15,28 +46,209 @@ pub struct App {
     slides: Slides,
 
     content: String,
+
+    output_window: WindowSettings,
+    output_window_open: bool,
+    /// How many monitors there are, once known.
+    monitors: Option<usize>,
+
+    stage: StageDisplay,
+    messages: MessagePanel,
+    history: History,
+
+    /// Puts the notes in exports.
+    export_notes: bool,
+    /// Where the last export went, or why it failed.
+    exported: Option<String>,
+
+    shortcuts: Shortcuts,
+    focus_editor: bool,
+    /// Asking whether to quit.
+    quitting: bool,
+    quit: bool,
 }
 
 unsafe impl Send for App {}
 
 impl App {
     pub fn from(root: RootPath) -> Self {
+        let project = Project::load(root.path.as_str());
+        let settings = Settings {
+            images: Images::from(root.path.as_str()),
+            theme: Arc::new(Theme::load(root.path.as_str())),
+            canvas: egui::vec2(project.canvas.width, project.canvas.height),
+            transition: project.transition,
+            service_name: project.service.name,
+        };
+        let stage = StageDisplay::from(&settings, project.stage);
+        let mut output = Display::from(settings.clone());
+        output.transitions = true;
+        output.autoplay = true;
+        output.clear_fade = project.clear_fade;
         Self {
             root,
-            preview: Display::default(),
-            output: Display::default(),
+            preview: Display::from(settings),
+            output,
             slides: Slides::default(),
 
             content: "".to_owned(),
+
+            output_window: project.output_window,
+            output_window_open: true,
+            monitors: None,
+
+            stage,
+            messages: MessagePanel::from(project.message_templates),
+            history: History::default(),
+
+            export_notes: false,
+            exported: None,
+
+            shortcuts: Shortcuts::from(project.keymap),
+            focus_editor: false,
+            quitting: false,
+            quit: false,
         }
     }
 
     pub fn output(&self) -> &Display {
-        return &self.preview; // FIXME: Change to &self.output when we're able to commit messages.
+        return &self.output;
+    }
+
+    /// Keeps track of how many monitors there are. Windows wait for this,
+    /// and with only one monitor the output doesn't open by itself.
+    pub fn set_monitors(&mut self, monitors: usize) {
+        if self.monitors.is_none() && monitors < 2 {
+            self.output_window_open = false;
+        }
+        self.monitors = Some(monitors);
+    }
+
+    /// How the output window should be, or `None` when it should be
+    /// closed.
+    pub fn output_window(&self) -> Option<WindowSettings> {
+        let monitors = self.monitors.filter(|_| self.output_window_open)?;
+        Some(self.output_window.on_monitors(monitors))
+    }
+
+    /// Keeps track of the output window being closed some other way, like
+    /// by the window manager.
+    pub fn output_window_closed(&mut self) {
+        self.output_window_open = false;
+    }
+
+    /// How the stage window should be, or `None` when it should be closed.
+    pub fn stage_window(&self) -> Option<WindowSettings> {
+        let monitors = self.monitors.filter(|_| self.stage.open)?;
+        Some(self.stage.stage.window.on_monitors(monitors))
+    }
+
+    pub fn stage_window_closed(&mut self) {
+        self.stage.open = false;
+    }
+
+    /// Asks whether to quit, instead of quitting on a stray key.
+    pub fn request_quit(&mut self) {
+        self.quitting = true;
+    }
+
+    /// Whether quitting was asked for and confirmed.
+    pub fn quit_confirmed(&self) -> bool {
+        self.quit
+    }
+
+    pub fn perform(&mut self, action: Action) {
+        match action {
+            Action::NextSlide => self.output.next(),
+            Action::PreviousSlide => self.output.previous(),
+            Action::NextItem => self.slides.select_next(),
+            Action::PreviousItem => self.slides.select_previous(),
+            Action::GoLive => self.take(),
+            Action::Undo => self.undo(),
+            Action::Resume => self.output.resume(),
+            Action::ClearText | Action::ClearBackground | Action::Black | Action::Logo => {
+                if let Some((_, clear)) = CLEAR_ACTIONS.into_iter().find(|(clearing, _)| *clearing == action) {
+                    self.toggle_clear(clear);
+                }
+            }
+            Action::ShowAll => self.output.clear(Clear::None),
+            Action::Section(kind) => self.jump_to_section(kind, None),
+            Action::FocusEditor => self.focus_editor = true,
+            Action::OutputWindow => self.output_window_open = !self.output_window_open,
+            Action::StageWindow => self.stage.open = !self.stage.open,
+            Action::Quit => self.request_quit(),
+        }
+    }
+
+    /// Performs the actions whose keys were pressed in `ctx`'s window.
+    fn handle_keys(&mut self, ctx: &egui::Context) {
+        for (action, number) in self.shortcuts.pressed(ctx) {
+            match action {
+                Action::Section(kind) => self.jump_to_section(kind, number),
+                action => self.perform(action),
+            }
+        }
+    }
+
+    /// Jumps the output to a section of what's live, like the second
+    /// verse. Without a number it's the next section of the kind.
+    pub fn jump_to_section(&mut self, kind: SectionKind, number: Option<u32>) {
+        let index = self.output.index();
+        let start = self.output.file().and_then(|file| file.sections.find(kind, number, index));
+        if let Some(start) = start {
+            self.output.select(start);
+        }
+    }
+
+    /// Sends the preview live: the output shows the preview's content, at
+    /// the same slide and build. A clear stays on until it's lifted.
+    pub fn take(&mut self) {
+        self.output.take(&self.preview);
+    }
+
+    /// Steps the output back to the slide that was live before, for when
+    /// it moved on by accident.
+    pub fn undo(&mut self) {
+        self.history.undo(&mut self.output);
+    }
+
+    /// Clears the output, or shows everything again when it's already
+    /// cleared that way. The slide stays where it is.
+    pub fn toggle_clear(&mut self, clear: Clear) {
+        if self.output.cleared() == clear {
+            self.output.clear(Clear::None);
+        } else {
+            self.output.clear(clear);
+        }
+    }
+
+    /// Writes the text of what's edited to `export.txt` in the root
+    /// folder, for lyrics sheets and the like.
+    pub fn export(&mut self) {
+        let path = Path::new(&self.root.path).join(EXPORT_FILE);
+        let text = self.preview.export_text(self.export_notes);
+        self.exported = Some(match std::fs::write(&path, text) {
+            Ok(()) => format!("exported to {}", path.display()),
+            Err(error) => format!("couldn't export: {error}"),
+        });
+    }
+
+    /// Moves the clock display animations run on, in seconds.
+    pub fn set_time(&mut self, seconds: f64) {
+        self.preview.set_time(seconds);
+        self.output.set_time(seconds);
+        self.stage.set_time(seconds);
+        self.stage.update(&self.output);
+        self.history.record(&self.output);
     }
 
     fn update_slides_if_needed(&mut self) {
         self.slides.update_if_needed();
+        if let Some(index) = self.slides.take_clicked_section() {
+            self.output.select(index);
+        }
+        let sections = self.output.file().map_or(&[][..], |file| file.sections.view());
+        self.slides.set_sections(sections, self.output.index());
         if self.content != self.preview.content {
             self.preview.content = self.content.clone();
             self.preview.update();
@@ -45,19 +257,84 @@ impl App {
 
     pub fn draw_control_window(&mut self, ctx: &egui::Context) {
         self.update_slides_if_needed();
+        // Taken before the editor sees the keys, so going live doesn't add
+        // a line.
+        self.handle_keys(ctx);
+        if self.quitting {
+            self.draw_quit_dialog(ctx);
+        }
 
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.with_layout(Layout::left_to_right(Align::Center), |ui| {
                 let available_size = ui.available_size();
                 ui.with_layout(Layout::top_down(Align::Min), |ui| {
-                    let label = ui.heading("preview");
+                    let label = ui.horizontal(|ui| {
+                        let label = ui.heading("preview");
+                        let index = self.preview.index();
+                        if ui.button("⏴").clicked() {
+                            self.preview.previous();
+                        }
+                        ui.label(format!("{}/{}", index + 1, self.preview.slide_count()));
+                        if ui.button("⏵").clicked() {
+                            self.preview.next();
+                        }
+                        let keys = self.shortcuts.describe(Action::GoLive);
+                        if ui.button("Go live").on_hover_text(keys).clicked() {
+                            self.take();
+                        }
+                        label
+                    }).inner;
                     ui.add_sized(available_size / 2.0, &mut self.preview)
                         .labelled_by(label.id);
+                    let notes = self.preview.notes();
+                    if !notes.is_empty() {
+                        ui.label(RichText::new(notes.join("\n")).italics());
+                    }
+                    let unknown = self.preview.unknown_placeholders();
+                    if !unknown.is_empty() {
+                        let names: Vec<String> = unknown.iter().map(|name| format!("{{{name}}}")).collect();
+                        ui.colored_label(Color32::RED, format!("unknown placeholders: {}", names.join(", ")));
+                    }
                 });
                 ui.with_layout(Layout::top_down(Align::Min), |ui| {
-                    let label = ui.heading("output");
-                    ui.add_sized(available_size / 2.0, &mut self.output)
+                    let label = ui.horizontal(|ui| {
+                        let label = ui.heading(RichText::new("● live").color(LIVE_COLOR));
+                        ui.label(format!("{}/{}", self.output.index() + 1, self.output.slide_count()));
+                        ui.separator();
+                        let last_monitor = self.monitors.unwrap_or(1).saturating_sub(1);
+                        ui.checkbox(&mut self.output_window_open, "window");
+                        ui.add(DragValue::new(&mut self.output_window.monitor)
+                            .clamp_range(0..=last_monitor)
+                            .prefix("monitor "));
+                        ui.checkbox(&mut self.output_window.fullscreen, "fullscreen");
+                        ui.separator();
+                        ui.checkbox(&mut self.stage.open, "stage");
+                        ui.add(DragValue::new(&mut self.stage.stage.window.monitor)
+                            .clamp_range(0..=last_monitor)
+                            .prefix("monitor "));
+                        label
+                    }).inner;
+                    ui.horizontal(|ui| {
+                        for ((action, clear), name) in CLEAR_ACTIONS.into_iter().zip(CLEAR_NAMES) {
+                            let button = ui.selectable_label(self.output.cleared() == clear, name);
+                            if button.on_hover_text(self.shortcuts.describe(action)).clicked() {
+                                self.toggle_clear(clear);
+                            }
+                        }
+                        let cleared = self.output.cleared() != Clear::None;
+                        let show_all = ui.add_enabled(cleared, Button::new("show all"));
+                        if show_all.on_hover_text(self.shortcuts.describe(Action::ShowAll)).clicked() {
+                            self.perform(Action::ShowAll);
+                        }
+                        if let Some(duration) = self.output.slide_duration() {
+                            ui.separator();
+                            self.autoplay_ui(ui, duration);
+                        }
+                    });
+                    let response = ui.add_sized(available_size / 2.0, &mut self.output)
                         .labelled_by(label.id);
+                    ui.painter().rect_stroke(response.rect, 0.0, Stroke::new(2.0, LIVE_COLOR));
+                    self.messages.ui(ui, &mut self.output);
                 });
             });
 
@@ -72,8 +349,30 @@ impl App {
                 });
 
                 ui.with_layout(Layout::top_down(Align::Min), |ui| {
-                    let label = ui.heading("edit");
-                    ui.add_sized(
+                    let width = ui.available_width() / 4.0;
+                    ui.set_width(width);
+                    ui.horizontal(|ui| {
+                        ui.heading("history");
+                        if ui.button("Undo").on_hover_text(self.shortcuts.describe(Action::Undo)).clicked() {
+                            self.undo();
+                        }
+                    });
+                    self.history.ui(ui, &mut self.output);
+                });
+
+                ui.with_layout(Layout::top_down(Align::Min), |ui| {
+                    let label = ui.horizontal(|ui| {
+                        let label = ui.heading("edit");
+                        if ui.button("Export").clicked() {
+                            self.export();
+                        }
+                        ui.checkbox(&mut self.export_notes, "with notes");
+                        if let Some(exported) = &self.exported {
+                            ui.label(exported.as_str());
+                        }
+                        label
+                    }).inner;
+                    let editor = ui.add_sized(
                         ui.available_size(),
                         egui::TextEdit::multiline(&mut self.content)
                             .clip_text(true)
@@ -81,12 +380,17 @@ impl App {
                             .lock_focus(false),
                     )
                     .labelled_by(label.id);
+                    if self.focus_editor {
+                        editor.request_focus();
+                        self.focus_editor = false;
+                    }
                 });
             });
         });
     }
 
     pub fn draw_display_window(&mut self, ctx: &egui::Context) {
+        self.handle_keys(ctx);
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.with_layout(Layout::left_to_right(Align::Center), |ui| {
                 ui.add_sized(
@@ -96,4 +400,55 @@ impl App {
             });
         });
     }
+
+    pub fn draw_stage_window(&mut self, ctx: &egui::Context) {
+        self.handle_keys(ctx);
+        egui::CentralPanel::default()
+            .frame(egui::Frame::none().fill(Color32::BLACK))
+            .show(ctx, |ui| self.stage.ui(ui));
+        // For the clock.
+        ctx.request_repaint_after(Duration::from_secs(1));
+    }
+
+    /// Shows how far the output is through a slide that moves on by
+    /// itself, with a way to pause and resume.
+    fn autoplay_ui(&mut self, ui: &mut egui::Ui, duration: f64) {
+        if self.output.is_paused() {
+            let resume = ui.button("Resume").on_hover_text(self.shortcuts.describe(Action::Resume));
+            if resume.clicked() {
+                self.output.resume();
+            }
+            ui.label("paused");
+            return;
+        }
+        if ui.button("Pause").clicked() {
+            self.output.pause();
+        }
+        let progress = self.output.slide_progress().unwrap_or(0.0).clamp(0.0, 1.0);
+        let remaining = duration * (1.0 - progress as f64);
+        ui.add(ProgressBar::new(progress).desired_width(120.0).text(format!("{:.0} s", remaining.ceil())));
+        ui.ctx().request_repaint();
+    }
+
+    fn draw_quit_dialog(&mut self, ctx: &egui::Context) {
+        if ctx.input_mut(|input| input.consume_key(Modifiers::NONE, Key::Escape)) {
+            self.quitting = false;
+            return;
+        }
+        egui::Window::new("Quit?")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label("The output and stage windows close too.");
+                ui.horizontal(|ui| {
+                    if ui.button("Quit").clicked() {
+                        self.quit = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        self.quitting = false;
+                    }
+                });
+            });
+    }
 }
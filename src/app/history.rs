@@ -0,0 +1,78 @@
+use chrono::{DateTime, Local};
+use egui::{ScrollArea, Ui};
+
+use display::{Display, Snapshot};
+
+/// How many slides the history keeps.
+const HISTORY_LENGTH: usize = 200;
+
+/// A slide that went live, and when.
+struct Aired {
+    snapshot: Snapshot,
+    at: DateTime<Local>,
+}
+
+/// Every slide sent to the output, however it got there, so a slip can
+/// be undone.
+#[derive(Default)]
+pub(crate) struct History {
+    /// The latest last.
+    aired: Vec<Aired>,
+}
+
+impl History {
+    /// Notes what `output` shows when it's a slide other than the last one.
+    pub fn record(&mut self, output: &Display) {
+        let Some(snapshot) = output.snapshot() else {
+            return;
+        };
+        match self.aired.last_mut() {
+            Some(last) if last.snapshot.same_slide(&snapshot) => last.snapshot = snapshot,
+            _ => {
+                if self.aired.len() == HISTORY_LENGTH {
+                    self.aired.remove(0);
+                }
+                self.aired.push(Aired {
+                    snapshot,
+                    at: Local::now(),
+                });
+            }
+        }
+    }
+
+    /// Drops the slide live now and shows the one before it again.
+    pub fn undo(&mut self, output: &mut Display) {
+        if self.aired.len() < 2 {
+            return;
+        }
+        self.aired.pop();
+        if let Some(previous) = self.aired.last() {
+            output.restore(&previous.snapshot);
+        }
+    }
+
+    /// Lists the slides, the latest first. Clicking one sends it live
+    /// again.
+    pub fn ui(&mut self, ui: &mut Ui, output: &mut Display) {
+        ScrollArea::vertical().id_source("history").show(ui, |ui| {
+            let mut picked = None;
+            for (index, aired) in self.aired.iter().enumerate().rev() {
+                let snapshot = &aired.snapshot;
+                let text = format!(
+                    "{}  {} {}/{}",
+                    aired.at.format("%H:%M:%S"),
+                    snapshot.song_title(),
+                    snapshot.index + 1,
+                    snapshot.slide_count()
+                );
+                let live = index + 1 == self.aired.len();
+                if ui.selectable_label(live, text).clicked() && !live {
+                    picked = Some(index);
+                }
+            }
+            if let Some(index) = picked {
+                output.restore(&self.aired[index].snapshot);
+            }
+        });
+    }
+}
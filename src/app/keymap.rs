@@ -0,0 +1,117 @@
+use egui::{Key, KeyboardShortcut, Modifiers};
+
+use project::{Action, Keymap};
+
+const KEYS: [Key; 53] = [
+    Key::ArrowDown, Key::ArrowLeft, Key::ArrowRight, Key::ArrowUp,
+    Key::Escape, Key::Tab, Key::Backspace, Key::Enter, Key::Space,
+    Key::Insert, Key::Delete, Key::Home, Key::End, Key::PageUp, Key::PageDown,
+    Key::Minus, Key::PlusEquals,
+    Key::Num0, Key::Num1, Key::Num2, Key::Num3, Key::Num4,
+    Key::Num5, Key::Num6, Key::Num7, Key::Num8, Key::Num9,
+    Key::A, Key::B, Key::C, Key::D, Key::E, Key::F, Key::G, Key::H, Key::I, Key::J, Key::K, Key::L, Key::M,
+    Key::N, Key::O, Key::P, Key::Q, Key::R, Key::S, Key::T, Key::U, Key::V, Key::W, Key::X, Key::Y, Key::Z,
+];
+
+const FUNCTION_KEYS: [Key; 20] = [
+    Key::F1, Key::F2, Key::F3, Key::F4, Key::F5, Key::F6, Key::F7, Key::F8, Key::F9, Key::F10,
+    Key::F11, Key::F12, Key::F13, Key::F14, Key::F15, Key::F16, Key::F17, Key::F18, Key::F19, Key::F20,
+];
+
+/// Digits for numbered sections, zero being the tenth.
+const DIGITS: [Key; 10] = [
+    Key::Num1, Key::Num2, Key::Num3, Key::Num4, Key::Num5, Key::Num6, Key::Num7, Key::Num8, Key::Num9, Key::Num0,
+];
+
+/// The keys of a `Keymap`, read into shortcuts egui can match.
+pub(crate) struct Shortcuts {
+    keymap: Keymap,
+    shortcuts: Vec<(KeyboardShortcut, Action)>,
+    /// Section keys held down while pressing a digit, which don't jump
+    /// again once let go.
+    numbered: Vec<Key>,
+}
+
+impl Shortcuts {
+    /// Keys that can't be read are left out.
+    pub fn from(keymap: Keymap) -> Self {
+        let shortcuts = keymap
+            .bindings
+            .iter()
+            .filter_map(|(keys, action)| Some((parse_shortcut(keys)?, *action)))
+            .collect();
+        Self {
+            keymap,
+            shortcuts,
+            numbered: Vec::new(),
+        }
+    }
+
+    /// Actions whose keys were pressed this frame, taking the keys. While a
+    /// text field has focus only shortcuts holding Ctrl or on function keys
+    /// count, so typing doesn't move the output.
+    ///
+    /// Section keys act when let go, so a digit can be pressed while
+    /// holding them. The digit comes with the action.
+    pub fn pressed(&mut self, ctx: &egui::Context) -> Vec<(Action, Option<u32>)> {
+        let typing = ctx.wants_keyboard_input();
+        let mut pressed = Vec::new();
+        for (shortcut, action) in &self.shortcuts {
+            if typing && !works_while_typing(shortcut) {
+                continue;
+            }
+            if !matches!(action, Action::Section(_)) {
+                if ctx.input_mut(|input| input.consume_shortcut(shortcut)) {
+                    pressed.push((*action, None));
+                }
+                continue;
+            }
+            ctx.input_mut(|input| {
+                let modifiers = input.modifiers.matches(shortcut.modifiers);
+                if modifiers && input.key_down(shortcut.key) {
+                    let digit = DIGITS.iter().position(|digit| input.consume_key(Modifiers::NONE, *digit));
+                    if let Some(digit) = digit {
+                        self.numbered.push(shortcut.key);
+                        pressed.push((*action, Some(digit as u32 + 1)));
+                    }
+                }
+                if input.key_released(shortcut.key) {
+                    if modifiers && !self.numbered.contains(&shortcut.key) {
+                        pressed.push((*action, None));
+                    }
+                    self.numbered.retain(|key| *key != shortcut.key);
+                }
+            });
+        }
+        pressed
+    }
+
+    /// The keys bound to `action`, for hover texts.
+    pub fn describe(&self, action: Action) -> String {
+        self.keymap.keys(action).collect::<Vec<_>>().join(", ")
+    }
+}
+
+fn works_while_typing(shortcut: &KeyboardShortcut) -> bool {
+    shortcut.modifiers.command || FUNCTION_KEYS.contains(&shortcut.key)
+}
+
+/// Reads keys like `PageDown`, `F3` or `Ctrl+Shift+Q`. Letters and names
+/// are the ones egui shows, in any case.
+fn parse_shortcut(s: &str) -> Option<KeyboardShortcut> {
+    let (modifier_names, key) = s.rsplit_once('+').unwrap_or(("", s));
+    let mut modifiers = Modifiers::NONE;
+    for name in modifier_names.split('+').filter(|name| !name.is_empty()) {
+        match name.to_ascii_lowercase().as_str() {
+            "ctrl" | "cmd" => modifiers.command = true,
+            "shift" => modifiers.shift = true,
+            "alt" => modifiers.alt = true,
+            _ => return None,
+        }
+    }
+    let key = KEYS
+        .into_iter()
+        .chain(FUNCTION_KEYS)
+        .find(|candidate| candidate.name().eq_ignore_ascii_case(key))?;
+    Some(KeyboardShortcut::new(modifiers, key))
+}
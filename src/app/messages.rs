@@ -0,0 +1,101 @@
+use egui::{Button, ComboBox, DragValue, Key, TextEdit, Ui};
+
+use display::{Display, Message};
+use project::{MessageTemplate, MAX_MESSAGE_DURATION};
+
+/// How many messages the history keeps.
+const HISTORY_LENGTH: usize = 20;
+
+/// Where the operator writes messages to show over the output, starting
+/// from a template or one shown before.
+pub(crate) struct MessagePanel {
+    text: String,
+    ticker: bool,
+    /// In seconds, zero showing it until it's dismissed.
+    duration: f64,
+    templates: Vec<MessageTemplate>,
+    /// Messages shown, the latest first.
+    history: Vec<Message>,
+}
+
+impl MessagePanel {
+    pub fn from(templates: Vec<MessageTemplate>) -> Self {
+        Self {
+            text: String::new(),
+            ticker: false,
+            duration: 0.0,
+            templates,
+            history: Vec::new(),
+        }
+    }
+
+    fn message(&self) -> Message {
+        Message {
+            text: self.text.trim().to_owned(),
+            ticker: self.ticker,
+            duration: (self.duration > 0.0).then_some(self.duration),
+        }
+    }
+
+    fn fill(&mut self, message: Message) {
+        self.text = message.text;
+        self.ticker = message.ticker;
+        self.duration = message.duration.unwrap_or(0.0);
+    }
+
+    /// Shows the message written on `output`, keeping it in the history.
+    fn show(&mut self, output: &mut Display) {
+        let message = self.message();
+        if message.text.is_empty() {
+            return;
+        }
+        self.history.retain(|shown| shown.text != message.text);
+        self.history.insert(0, message.clone());
+        self.history.truncate(HISTORY_LENGTH);
+        output.show_message(message);
+    }
+
+    pub fn ui(&mut self, ui: &mut Ui, output: &mut Display) {
+        ui.horizontal(|ui| {
+            ui.label("message");
+            let field = ui.add(
+                TextEdit::singleline(&mut self.text)
+                    .hint_text("Parent of child #12 please come to the nursery")
+                    .desired_width(ui.available_width() / 2.0),
+            );
+            let entered = field.lost_focus() && ui.input(|input| input.key_pressed(Key::Enter));
+            ui.checkbox(&mut self.ticker, "ticker");
+            ui.add(DragValue::new(&mut self.duration).clamp_range(0.0..=MAX_MESSAGE_DURATION).suffix(" s"))
+                .on_hover_text("How long it shows, zero until dismissed");
+            if ui.button("Show").clicked() || entered {
+                self.show(output);
+            }
+            if ui.add_enabled(output.message().is_some(), Button::new("Dismiss")).clicked() {
+                output.dismiss_message();
+            }
+
+            let mut picked = None;
+            ComboBox::from_id_source("message templates").selected_text("templates").show_ui(ui, |ui| {
+                for template in &self.templates {
+                    if ui.selectable_label(false, template.text.as_str()).clicked() {
+                        picked = Some(Message {
+                            text: template.text.clone(),
+                            ticker: template.ticker,
+                            duration: template.duration,
+                        });
+                    }
+                }
+            });
+            ComboBox::from_id_source("message history").selected_text("history").show_ui(ui, |ui| {
+                for message in &self.history {
+                    if ui.selectable_label(false, message.text.as_str()).clicked() {
+                        picked = Some(message.clone());
+                    }
+                }
+            });
+            if let Some(message) = picked {
+                self.fill(message);
+            }
+        });
+    }
+}
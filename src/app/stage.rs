@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use chrono::Local;
+use egui::{Align, Color32, Layout, RichText, Ui};
+
+use display::{Display, Settings};
+use project::{Stage, StageLayout};
+use slide::format_duration;
+
+/// Dims what isn't live.
+const NEXT_COLOR: Color32 = Color32::from_gray(160);
+/// Sets notes apart from the slides.
+const NOTES_COLOR: Color32 = Color32::from_rgb(240, 200, 80);
+
+/// What the band sees: the live slide, the one after it, the time, how
+/// long the service and the slide have been going, and the slide's notes.
+/// Slides are drawn without their backgrounds or the theme, so the text
+/// stands out.
+pub(crate) struct StageDisplay {
+    current: Display,
+    next: Display,
+    pub stage: Stage,
+    pub open: bool,
+    /// When the first slide went live.
+    live_since: Option<f64>,
+    /// When the live slide went live.
+    slide_since: f64,
+    now: f64,
+}
+
+impl StageDisplay {
+    pub fn from(settings: &Settings, stage: Option<Stage>) -> Self {
+        let settings = Settings {
+            theme: Arc::default(),
+            ..settings.clone()
+        };
+        let mut current = Display::from(settings.clone());
+        current.backgrounds = false;
+        let mut next = Display::from(settings);
+        next.backgrounds = false;
+        Self {
+            current,
+            next,
+            open: stage.is_some(),
+            stage: stage.unwrap_or_default(),
+            live_since: None,
+            slide_since: 0.0,
+            now: 0.0,
+        }
+    }
+
+    pub fn set_time(&mut self, seconds: f64) {
+        self.now = seconds;
+        self.current.set_time(seconds);
+        self.next.set_time(seconds);
+    }
+
+    /// Follows what `output` shows.
+    pub fn update(&mut self, output: &Display) {
+        let changed = self.current.content != output.content || self.current.index() != output.index();
+        self.current.take(output);
+        // Builds show in full, so the band knows what's coming.
+        let next = output.index() + 1;
+        if next < output.slide_count() {
+            self.next.show(output, next, usize::MAX);
+        } else if !self.next.content.is_empty() {
+            self.next.content.clear();
+            self.next.update();
+        }
+        if changed {
+            self.slide_since = self.now;
+            self.live_since.get_or_insert(self.now);
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut Ui) {
+        let available = ui.available_size();
+        match self.stage.layout {
+            StageLayout::Side => {
+                ui.with_layout(Layout::left_to_right(Align::Min), |ui| {
+                    ui.add_sized([available.x * 2.0 / 3.0, available.y], &mut self.current);
+                    ui.with_layout(Layout::top_down(Align::Min), |ui| {
+                        self.side_ui(ui, available.y / 3.0);
+                    });
+                });
+            }
+            StageLayout::Stacked => {
+                ui.with_layout(Layout::top_down(Align::Min), |ui| {
+                    ui.add_sized([available.x, available.y * 2.0 / 3.0], &mut self.current);
+                    ui.with_layout(Layout::left_to_right(Align::Min), |ui| {
+                        self.side_ui(ui, available.y / 3.0);
+                    });
+                });
+            }
+        }
+    }
+
+    /// The next slide, the clock and the timers.
+    fn side_ui(&mut self, ui: &mut Ui, height: f32) {
+        let font_size = self.stage.font_size;
+        ui.vertical(|ui| {
+            ui.label(RichText::new("next").size(font_size / 2.0).color(NEXT_COLOR));
+            ui.add_sized([height * 16.0 / 9.0, height], &mut self.next);
+        });
+        ui.vertical(|ui| {
+            ui.label(RichText::new(Local::now().format("%H:%M:%S").to_string()).size(font_size).strong());
+            if let Some(live_since) = self.live_since {
+                let service = format_duration((self.now - live_since).floor());
+                ui.label(RichText::new(format!("service {service}")).size(font_size / 2.0));
+            }
+            let slide = format_duration((self.now - self.slide_since).max(0.0).floor());
+            ui.label(RichText::new(format!("slide {slide}")).size(font_size / 2.0));
+            for note in self.current.notes() {
+                ui.label(RichText::new(*note).size(font_size * 0.75).color(NOTES_COLOR));
+            }
+        });
+    }
+}
@@ -1,71 +1,801 @@
-use egui::RichText;
-use egui::{Response, Ui, Widget };
-use egui::{Align, Layout, Color32};
-use sil::{AST, Token};
-use slide::{Layer, Layers};
-
-struct Slide {
-    tokens: Vec<Token>,
+mod draw;
+mod fit;
+mod images;
+mod layout;
+mod list;
+mod message;
+mod placeholders;
+mod table;
+mod timer;
+
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use chrono::Local;
+use egui::{Response, Sense, Ui, Widget };
+use egui::{vec2, Color32, Vec2};
+use sil::{AstNode, SilFile, AST, Token};
+use slide::{BackgroundLayer, Deck, Easing, Footer, ImageLayer, Layer, ListStyle, TableStyle};
+use slide::{export_text, Presentation, Transition, TransitionKind};
+
+use crate::draw::{BuildState, SlidePainter};
+use crate::fit::FontSizes;
+use crate::layout::Frame;
+use crate::message::Showing;
+use crate::placeholders::{shows_time, Placeholders};
+use crate::timer::Clock;
+
+pub use crate::images::{ImageState, Images};
+pub use crate::message::Message;
+
+// How long a build part takes to enter, in seconds.
+const BUILD_DURATION: f64 = 0.4;
+
+struct Document {
+    // Only kept alive for the AST, which points into it.
+    _tokens: Vec<Token>,
     ast: AST,
-    layers: Layers,
+    deck: Deck,
+    file: SilFile,
+    presentation: Presentation,
+    font_sizes: OnceLock<FontSizes>,
+    // Everything above points into `content`, so it's dropped last.
+    content: String,
+}
+
+impl Document {
+    fn from(content: String) -> Self {
+        // SAFETY: The string's buffer doesn't move with it, and it's only
+        // dropped together with the tokens, AST and deck pointing into it.
+        let text = unsafe { &*(content.as_str() as *const str) };
+        let tokens = sil::lex(text);
+        let ast = sil::parse(&tokens);
+        let deck = Deck::from(&ast);
+        let file = SilFile::from_ast(text, &ast);
+        let presentation = Presentation::from_ast(&ast);
+        Document {
+            _tokens: tokens,
+            ast,
+            deck,
+            file,
+            presentation,
+            font_sizes: OnceLock::new(),
+            content,
+        }
+    }
+
+    /// The song's title, or the text of its first title without one.
+    fn song_title(&self) -> &'static str {
+        if let Some(title) = self.file.meta.title {
+            return title;
+        }
+        self.deck
+            .view()
+            .iter()
+            .flat_map(|slide| slide.layers.view())
+            .find_map(|layer| match layer {
+                Layer::Title(title) => Some(title.text),
+                _ => None,
+            })
+            .unwrap_or("")
+    }
+}
+
+/// Defaults for every slide, loaded from `theme.sil` in the project root.
+pub struct Theme {
+    document: Document,
+    transition: Transition,
+    footer: Option<Footer>,
+    logo: Option<ImageLayer>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::from(String::new())
+    }
+}
+
+impl Theme {
+    pub fn from(content: String) -> Self {
+        let document = Document::from(content);
+        let ast = &document.ast;
+        let transition = ast
+            .view()
+            .iter()
+            .rev()
+            .find_map(|block| match block {
+                &AstNode::Node(id) if ast[id].kind == "transition" => Some(Transition::from(&ast[id])),
+                _ => None,
+            })
+            .unwrap_or_default();
+        let footer = ast.view().iter().rev().find_map(|block| match block {
+            &AstNode::Node(id) if ast[id].kind == "footer" => Some(Footer::from(&ast[id])),
+            _ => None,
+        });
+        let logo = ast.view().iter().rev().find_map(|block| match block {
+            &AstNode::Node(id) if ast[id].kind == "logo" => Some(ImageLayer::from(&ast[id])),
+            _ => None,
+        });
+        Self {
+            document,
+            transition,
+            footer,
+            logo,
+        }
+    }
+
+    pub fn load(root: &str) -> Self {
+        let path = std::path::Path::new(root).join("theme.sil");
+        Self::from(std::fs::read_to_string(path).unwrap_or_default())
+    }
+
+    pub fn content(&self) -> &str {
+        self.document.content.as_str()
+    }
+
+    pub fn background(&self) -> Option<&BackgroundLayer> {
+        self.document.deck.view().iter().find_map(|slide| slide.layers.background())
+    }
+
+    pub fn transition(&self) -> Transition {
+        self.transition
+    }
+
+    /// What the output shows when cleared to the logo, from the theme's
+    /// `[logo]` block.
+    pub fn logo(&self) -> Option<&ImageLayer> {
+        self.logo.as_ref()
+    }
+
+    /// The copyright footer, if the theme has one.
+    pub fn footer(&self) -> Option<Footer> {
+        self.footer
+    }
+
+    /// Styling from the theme's `[list]` block.
+    pub fn list_style(&self) -> ListStyle {
+        self.layers()
+            .find_map(|layer| match layer {
+                Layer::List(list) => Some(list.style),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    /// Styling from the theme's `[table]` block.
+    pub fn table_style(&self) -> TableStyle {
+        self.layers()
+            .find_map(|layer| match layer {
+                Layer::Table(table) => Some(table.style),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    /// Layers drawn on every slide, like lower-third bars and logos.
+    pub(crate) fn decorations(&self) -> impl Iterator<Item = &Layer> {
+        self.layers().filter(|layer| match layer {
+            Layer::Text(_) | Layer::Title(_) | Layer::Image(_) | Layer::Shape(_) => true,
+            Layer::Qr(_) | Layer::Timer(_) => true,
+            // Lists and tables only style the slides' own.
+            _ => false,
+        })
+    }
+
+    fn layers(&self) -> impl Iterator<Item = &Layer> {
+        self.document.deck.view().iter().flat_map(|slide| slide.layers.view())
+    }
+}
+
+/// What every display of a project shares.
+#[derive(Clone)]
+pub struct Settings {
+    pub images: Images,
+    pub theme: Arc<Theme>,
+    /// Size of the virtual canvas, in canvas units.
+    pub canvas: Vec2,
+    /// Used by slides and themes that don't pick their own.
+    pub transition: Transition,
+    /// What `{service.name}` shows.
+    pub service_name: String,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            images: Images::default(),
+            theme: Arc::default(),
+            canvas: vec2(1920.0, 1080.0),
+            transition: Transition::default(),
+            service_name: String::new(),
+        }
+    }
+}
+
+/// What a display hides, while keeping its place in the deck.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Clear {
+    #[default]
+    None,
+    /// Hides the layers, keeping the background.
+    Text,
+    /// Hides the background, keeping the layers.
+    Background,
+    /// Hides everything, down to black.
+    All,
+    /// Hides everything, showing the theme's logo instead.
+    Logo,
+}
+
+impl Clear {
+    /// How much of the background, the layers and the logo shows.
+    fn opacities(self) -> [f32; 3] {
+        match self {
+            Clear::None => [1.0, 1.0, 0.0],
+            Clear::Text => [1.0, 0.0, 0.0],
+            Clear::Background => [0.0, 1.0, 0.0],
+            Clear::All => [0.0, 0.0, 0.0],
+            Clear::Logo => [0.0, 0.0, 1.0],
+        }
+    }
+}
+
+/// A slide a display showed, down to its build.
+#[derive(Clone)]
+pub struct Snapshot {
+    document: Arc<Document>,
+    pub index: usize,
+    pub revealed: usize,
+}
+
+impl Snapshot {
+    /// Whether both are of the same slide of the same content, builds
+    /// aside.
+    pub fn same_slide(&self, other: &Snapshot) -> bool {
+        self.index == other.index
+            && (Arc::ptr_eq(&self.document, &other.document) || self.document.content == other.document.content)
+    }
+
+    pub fn song_title(&self) -> &str {
+        self.document.song_title()
+    }
+
+    pub fn slide_count(&self) -> usize {
+        self.document.deck.view().len()
+    }
+}
+
+/// The slide being transitioned away from. It keeps its document alive
+/// until the transition is done.
+struct Outgoing {
+    document: Arc<Document>,
+    index: usize,
+    revealed: usize,
+    shown_at: f64,
+    transition: Transition,
+    started: f64,
 }
 
 pub struct Display {
     pub content: String,
-    slide: Option<Slide>
+    /// Animate between slides instead of cutting.
+    pub transitions: bool,
+    /// How long clearing fades for, in seconds. Zero cuts.
+    pub clear_fade: f32,
+    /// Draw slide backgrounds. The stage display goes without.
+    pub backgrounds: bool,
+    /// Move on by itself after slides' durations, unless paused.
+    pub autoplay: bool,
+    /// Moving by hand pauses autoplay.
+    paused: bool,
+    resumed_at: f64,
+    document: Option<Arc<Document>>,
+    index: usize,
+    /// Parts of the current slide's build shown so far.
+    revealed: usize,
+    /// When the last part was revealed by `next`.
+    revealed_at: Option<f64>,
+    /// When the current slide was selected, for its timers.
+    shown_at: f64,
+    outgoing: Option<Outgoing>,
+    clear: Clear,
+    /// What was cleared before, and when that changed.
+    cleared_from: Clear,
+    cleared_at: f64,
+    message: Option<Showing>,
+    now: f64,
+    settings: Settings,
 }
 
 impl Default for Display {
     fn default() -> Self {
+        Self::from(Settings::default())
+    }
+}
+
+impl Display {
+    pub fn from(settings: Settings) -> Self {
         Self {
             content: "Lorem ipsum dolor sit amet".to_owned(),
-            slide: None,
+            transitions: false,
+            clear_fade: 0.0,
+            backgrounds: true,
+            autoplay: false,
+            paused: false,
+            resumed_at: 0.0,
+            document: None,
+            index: 0,
+            revealed: 1,
+            revealed_at: None,
+            shown_at: 0.0,
+            outgoing: None,
+            clear: Clear::None,
+            cleared_from: Clear::None,
+            cleared_at: 0.0,
+            message: None,
+            now: 0.0,
+            settings,
         }
     }
-}
 
-impl Display {
     pub fn update(&mut self) {
-        let mut slide = Slide {
-            tokens: Vec::new(),
-            ast: AST::new(),
-            layers: Layers::default()
+        let previous = self.document.take().map(|document| (document, self.index, self.revealed, self.shown_at));
+        self.document = Some(Arc::new(Document::from(self.content.clone())));
+        self.index = self.index.min(self.slide_count().saturating_sub(1));
+        self.revealed = self.revealed.min(self.part_count());
+        self.begin_transition(previous);
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn slide_count(&self) -> usize {
+        self.document.as_ref().map_or(0, |document| document.deck.view().len())
+    }
+
+    /// Selects slide `index` by hand, pausing autoplay.
+    pub fn select(&mut self, index: usize) {
+        self.pause();
+        self.go_to(index);
+    }
+
+    fn go_to(&mut self, index: usize) {
+        let index = index.min(self.slide_count().saturating_sub(1));
+        if index == self.index {
+            return;
+        }
+        let previous = self.document.clone().map(|document| (document, self.index, self.revealed, self.shown_at));
+        self.index = index;
+        self.revealed = 1;
+        self.revealed_at = None;
+        self.shown_at = self.now;
+        self.begin_transition(previous);
+    }
+
+    /// The file shown, as read when it was set.
+    pub fn file(&self) -> Option<&SilFile> {
+        self.document.as_ref().map(|document| &document.file)
+    }
+
+    /// The notes of the current slide. They're never drawn by the display
+    /// itself.
+    pub fn notes(&self) -> &[&'static str] {
+        self.document
+            .as_ref()
+            .and_then(|document| document.deck.view().get(self.index))
+            .map_or(&[], |slide| slide.notes.as_slice())
+    }
+
+    /// What's shown as plain text, with the notes if `notes` asks for
+    /// them.
+    pub fn export_text(&self, notes: bool) -> String {
+        self.document.as_ref().map_or_else(String::new, |document| export_text(&document.deck, notes))
+    }
+
+    /// Placeholders in what's shown that don't name anything. They're
+    /// drawn as typed.
+    pub fn unknown_placeholders(&self) -> &[&'static str] {
+        self.document.as_ref().map_or(&[], |document| document.file.unknown_placeholders.as_slice())
+    }
+
+    /// Parts of the current slide's build shown so far.
+    pub fn revealed(&self) -> usize {
+        self.revealed
+    }
+
+    /// Shows what `other` shows, down to its build, transitioning to it
+    /// like any other change. Timers only restart on a new slide.
+    /// Autoplay starts over with it.
+    pub fn take(&mut self, other: &Display) {
+        self.show(other, other.index, other.revealed);
+        self.resume();
+    }
+
+    /// Shows slide `index` of what `other` shows, with `revealed` parts of
+    /// its build, like `take`.
+    pub fn show(&mut self, other: &Display, index: usize, revealed: usize) {
+        self.show_document(&other.content, other.document.clone(), index, revealed);
+    }
+
+    /// What's shown, to show again later with `restore`.
+    pub fn snapshot(&self) -> Option<Snapshot> {
+        Some(Snapshot {
+            document: self.document.clone()?,
+            index: self.index,
+            revealed: self.revealed,
+        })
+    }
+
+    /// Shows what was shown when `snapshot` was taken, like `take`, but
+    /// pausing autoplay.
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        self.pause();
+        let document = snapshot.document.clone();
+        self.show_document(&document.content, Some(document.clone()), snapshot.index, snapshot.revealed);
+    }
+
+    fn show_document(&mut self, content: &str, document: Option<Arc<Document>>, index: usize, revealed: usize) {
+        let previous = self.document.clone().map(|document| (document, self.index, self.revealed, self.shown_at));
+        let same_content = self.content == content;
+        if !same_content {
+            self.content = content.to_owned();
+        }
+        self.document = document;
+        let index = index.min(self.slide_count().saturating_sub(1));
+        let same_slide = same_content && self.index == index;
+        if self.index != index {
+            self.shown_at = self.now;
+        }
+        self.index = index;
+        let revealed = revealed.min(self.part_count());
+        if same_slide {
+            if revealed > self.revealed {
+                self.revealed_at = Some(self.now);
+            }
+            self.revealed = revealed;
+            return;
+        }
+        self.revealed = revealed;
+        self.revealed_at = None;
+        self.begin_transition(previous);
+    }
+
+    /// Hides part of what's shown, fading over `clear_fade`. The slide
+    /// and its build stay as they are underneath.
+    pub fn clear(&mut self, clear: Clear) {
+        if clear == self.clear {
+            return;
+        }
+        self.cleared_from = self.clear;
+        self.clear = clear;
+        self.cleared_at = self.now;
+    }
+
+    pub fn cleared(&self) -> Clear {
+        self.clear
+    }
+
+    /// How much of the background, the layers and the logo shows, and
+    /// whether that's still fading.
+    fn clear_opacities(&self) -> ([f32; 3], bool) {
+        let t = if self.clear_fade > 0.0 {
+            ((self.now - self.cleared_at) as f32 / self.clear_fade).clamp(0.0, 1.0)
+        } else {
+            1.0
         };
-        slide.tokens = unsafe {
-            sil::lex(&*(self.content.as_str() as *const str))
+        let (from, to) = (self.cleared_from.opacities(), self.clear.opacities());
+        ([0, 1, 2].map(|i| from[i] + (to[i] - from[i]) * t), t < 1.0)
+    }
+
+    /// Parts of the current slide's build.
+    fn part_count(&self) -> usize {
+        self.document.as_ref().map_or(1, |document| document.deck[self.index].part_count())
+    }
+
+    /// Reveals the next part of the current slide, or moves on to the next
+    /// slide once everything is shown.
+    pub fn next(&mut self) {
+        self.pause();
+        if self.revealed < self.part_count() {
+            self.revealed += 1;
+            self.revealed_at = Some(self.now);
+        } else {
+            self.select(self.index + 1);
+        }
+    }
+
+    /// Hides the last revealed part, or goes back to the previous slide,
+    /// fully built.
+    pub fn previous(&mut self) {
+        self.pause();
+        if self.revealed > 1 {
+            self.revealed -= 1;
+            self.revealed_at = None;
+        } else if self.index > 0 {
+            self.select(self.index - 1);
+            self.revealed = self.part_count();
+        }
+    }
+
+    fn build_state(&self) -> BuildState {
+        let entering = self
+            .revealed_at
+            .map_or(1.0, |revealed_at| ((self.now - revealed_at) / BUILD_DURATION) as f32);
+        BuildState {
+            revealed: self.revealed,
+            entering: Easing::EaseOut.apply(entering),
+        }
+    }
+
+    /// Shows `message` over everything else, in place of any message shown
+    /// before. The slide stays as it is.
+    pub fn show_message(&mut self, message: Message) {
+        self.message = Some(Showing::from(message, self.now));
+    }
+
+    /// Fades out the message shown, if any.
+    pub fn dismiss_message(&mut self) {
+        if let Some(showing) = &mut self.message {
+            showing.until = Some(showing.until.map_or(self.now, |until| until.min(self.now)));
+        }
+    }
+
+    /// The message shown, unless it's on its way out.
+    pub fn message(&self) -> Option<&Message> {
+        self.message.as_ref().filter(|showing| showing.is_up(self.now)).map(|showing| &showing.message)
+    }
+
+    /// Moves the clock transitions and timers run on, in seconds. Moves
+    /// on to the next slide when a countdown set to advance runs out.
+    pub fn set_time(&mut self, seconds: f64) {
+        let step = seconds - self.now;
+        self.now = seconds;
+        if self.message.as_ref().is_some_and(|showing| showing.is_gone(seconds)) {
+            self.message = None;
+        }
+        let Some(slide) = self.document.as_ref().and_then(|document| document.deck.view().get(self.index)) else {
+            return;
         };
-        slide.ast = sil::parse(&slide.tokens);
-        slide.layers = Layers::from(&slide.ast);
-        self.slide = Some(slide);
+        if self.clock(self.shown_at).ran_out(slide, step) {
+            self.go_to(self.index + 1);
+        } else if self.slide_progress().is_some_and(|progress| progress >= 1.0) {
+            self.advance();
+        }
+    }
+
+    /// Moves on after a slide's duration, starting over after the last
+    /// slide of a looping presentation.
+    fn advance(&mut self) {
+        let Some(document) = &self.document else {
+            return;
+        };
+        let count = document.deck.view().len();
+        if self.index + 1 < count {
+            self.go_to(self.index + 1);
+        } else if document.presentation.looping && count > 1 {
+            self.go_to(0);
+        } else {
+            // Nothing left to play.
+            self.pause();
+        }
+    }
+
+    /// How long the current slide shows before autoplay moves on, in
+    /// seconds.
+    pub fn slide_duration(&self) -> Option<f64> {
+        let document = self.document.as_ref()?;
+        document.deck.view().get(self.index)?.duration.or(document.presentation.duration)
+    }
+
+    /// How far autoplay is through the current slide, from 0 to 1, or
+    /// `None` when it isn't playing.
+    pub fn slide_progress(&self) -> Option<f32> {
+        if !self.autoplay || self.paused {
+            return None;
+        }
+        let duration = self.slide_duration()?;
+        let elapsed = self.now - self.shown_at.max(self.resumed_at);
+        Some((elapsed / duration) as f32)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Plays on from the current slide, giving it its full duration.
+    pub fn resume(&mut self) {
+        self.paused = false;
+        self.resumed_at = self.now;
+    }
+
+    fn paint_message(&self, ui: &Ui, frame: Frame) {
+        if let Some(showing) = &self.message {
+            showing.paint(ui, frame, self.now);
+            showing.request_repaint(ui.ctx(), self.now);
+        }
+    }
+
+    fn clock(&self, shown_at: f64) -> Clock {
+        Clock {
+            elapsed: self.now - shown_at,
+            local: Local::now(),
+        }
+    }
+
+    fn begin_transition(&mut self, previous: Option<(Arc<Document>, usize, usize, f64)>) {
+        if !self.transitions {
+            return;
+        }
+        // Changing both the content and the slide in one frame is a single
+        // transition from whatever was on screen before.
+        let previous = match self.outgoing.take() {
+            Some(outgoing) if outgoing.started == self.now => {
+                Some((outgoing.document, outgoing.index, outgoing.revealed, outgoing.shown_at))
+            }
+            _ => previous,
+        };
+        let (Some((document, index, revealed, shown_at)), Some(incoming)) = (previous, &self.document) else {
+            return;
+        };
+        let transition = incoming.deck[self.index]
+            .transition
+            .or(self.settings.theme.transition())
+            .or(self.settings.transition);
+        if transition.kind() == TransitionKind::Cut || transition.duration() == 0.0 {
+            return;
+        }
+        self.outgoing = Some(Outgoing {
+            document,
+            index,
+            revealed,
+            shown_at,
+            transition,
+            started: self.now,
+        });
+    }
+
+    /// How far along the transition is, from 0 to 1. Finished transitions
+    /// are dropped.
+    fn progress(&mut self) -> Option<f32> {
+        let outgoing = self.outgoing.as_ref()?;
+        let elapsed = (self.now - outgoing.started) as f32;
+        let duration = outgoing.transition.duration();
+        if elapsed >= duration {
+            self.outgoing = None;
+            return None;
+        }
+        Some(outgoing.transition.easing().apply(elapsed / duration))
     }
 }
 
 impl Widget for &mut Display {
     fn ui(self, ui: &mut Ui) -> Response {
-        if let Some(slide) = &self.slide {
-            if slide.layers.view().is_empty() {
-                return ui.monospace(self.content.as_str());
-            }
+        let Some(document) = self.document.clone() else {
+            return ui.label("");
+        };
+        if document.ast.view().is_empty() {
+            return ui.monospace(self.content.as_str());
+        }
+
+        let response = ui.allocate_rect(ui.max_rect(), Sense::hover());
+        let canvas = self.settings.canvas;
+        let frame = Frame::fit(canvas, response.rect);
+        ui.painter_at(response.rect).rect_filled(response.rect, 0.0, Color32::BLACK);
+        // Nothing fits in a collapsed panel, and egui can't lay out text
+        // at no size at all.
+        if frame.scale <= 0.0 {
+            return response;
+        }
 
-            let mut ui = ui.child_ui(ui.max_rect(), Layout::top_down(Align::Center));
-            for layer in slide.layers.view() {
-                match layer {
-                    Layer::Title(layer) => {
-                        ui.heading(RichText::new(layer.text).size(layer.font_size as f32));
-                    }
-                    Layer::Garbage(garbage) => {
-                        ui.label(RichText::new(garbage.text()).strong().color(Color32::RED));
-                    }
-                    Layer::GarbageNode(node) => {
-                        ui.label(RichText::new(node.text()).strong().color(Color32::RED));
-                    }
-                    Layer::Text(layer) => {
-                        ui.label(RichText::new(layer.text).size(layer.font_size as f32));
-                    }
+        let progress = self.progress();
+        let build = self.build_state();
+        let ([background_opacity, layers_opacity, logo_opacity], clearing) = self.clear_opacities();
+        if clearing {
+            ui.ctx().request_repaint();
+        }
+        let slide = &document.deck[self.index];
+        let layers: Vec<&Layer> = slide.layers.view().iter().chain(self.settings.theme.decorations()).collect();
+        if build.entering < 1.0 || layers.iter().any(|layer| matches!(layer, Layer::Timer(_))) {
+            ui.ctx().request_repaint();
+        } else if layers.iter().any(|layer| shows_time(layer)) {
+            ui.ctx().request_repaint_after(Duration::from_secs(1));
+        }
+        let settings = &self.settings;
+        let backgrounds = self.backgrounds;
+        // Draws a slide shifted by `offset` canvas widths, and the logo over
+        // it when given one.
+        let draw =
+            |slide: (&Document, usize, BuildState, Clock), offset: f32, background: f32, layers: f32, logo: f32| {
+                let (document, index, build, clock) = slide;
+                let song_author = document.file.meta.author();
+                let painter = SlidePainter {
+                    ui,
+                    images: &settings.images,
+                    theme: &settings.theme,
+                    frame: frame.translate(vec2(offset * canvas.x, 0.0)),
+                    clip: frame.rect,
+                    clock,
+                    meta: &document.file.meta,
+                    placeholders: Placeholders {
+                        song_title: document.song_title(),
+                        song_author: &song_author,
+                        service_name: &settings.service_name,
+                        slide_index: index,
+                        slide_count: document.deck.view().len(),
+                        local: clock.local,
+                    },
+                };
+                let slide = &document.deck[index];
+                let (background, layers) = (background * background_opacity, layers * layers_opacity);
+                if background > 0.0 && backgrounds {
+                    painter.background(slide, background);
                 }
+                if layers > 0.0 {
+                    let font_sizes = document.font_sizes.get_or_init(|| {
+                        ui.fonts(|fonts| FontSizes::fit(fonts, &document.deck, canvas, &settings.theme))
+                    });
+                    painter.layers(slide, font_sizes, index, layers, build);
+                }
+                if logo > 0.0 {
+                    painter.logo(logo);
+                }
+            };
+
+        let current = (document.as_ref(), self.index, build, self.clock(self.shown_at));
+        let (Some(outgoing), Some(t)) = (&self.outgoing, progress) else {
+            draw(current, 0.0, 1.0, 1.0, logo_opacity);
+            self.paint_message(ui, frame);
+            return response;
+        };
+        let previous = (
+            outgoing.document.as_ref(),
+            outgoing.index,
+            BuildState {
+                revealed: outgoing.revealed,
+                entering: 1.0,
+            },
+            self.clock(outgoing.shown_at),
+        );
+        match outgoing.transition.kind() {
+            TransitionKind::Cut => {
+                draw(current, 0.0, 1.0, 1.0, 0.0);
+            }
+            TransitionKind::Crossfade => {
+                draw(previous, 0.0, 1.0, 1.0 - t, 0.0);
+                draw(current, 0.0, t, t, 0.0);
             }
+            TransitionKind::FadeThroughBlack => {
+                if t < 0.5 {
+                    draw(previous, 0.0, 1.0 - 2.0 * t, 1.0 - 2.0 * t, 0.0);
+                } else {
+                    draw(current, 0.0, 2.0 * t - 1.0, 2.0 * t - 1.0, 0.0);
+                }
+            }
+            TransitionKind::Slide => {
+                draw(previous, 0.0, 1.0, 1.0, 0.0);
+                draw(current, 1.0 - t, 1.0, 1.0, 0.0);
+            }
+            TransitionKind::Push => {
+                draw(previous, -t, 1.0, 1.0, 0.0);
+                draw(current, 1.0 - t, 1.0, 1.0, 0.0);
+            }
+            TransitionKind::Dissolve => {
+                draw(current, 0.0, 1.0, 0.0, 0.0);
+                draw(previous, 0.0, 0.0, 1.0 - t, 0.0);
+                draw(current, 0.0, 0.0, t, 0.0);
+            }
+        }
+        if logo_opacity > 0.0 {
+            draw(current, 0.0, 0.0, 0.0, logo_opacity);
         }
+        self.paint_message(ui, frame);
+        ui.ctx().request_repaint();
 
-        ui.label("")
+        response
     }
 }
@@ -0,0 +1,700 @@
+use std::borrow::Cow;
+use std::f32::consts::TAU;
+use std::ops::Range;
+use std::sync::Arc;
+
+use egui::{pos2, vec2, Align2, Color32, FontId, Galley, Mesh, Painter, Pos2, Rect, Rgba, Shape, Stroke, Ui, Vec2};
+use sil::Meta;
+use slide::{Background, BackgroundLayer, BuildEffect, Fit, FontSize, Geometry, ImageLayer, Layer, Length, Slide};
+use slide::{ListLayer, QrLayer, ShapeKind, ShapeLayer, TableLayer, TextEffects, TimerLayer};
+
+use crate::fit::FontSizes;
+use crate::layout::{layout_text, Frame, Layout};
+use crate::list::layout_list;
+use crate::placeholders::Placeholders;
+use crate::table::layout_table;
+use crate::timer::Clock;
+use crate::{ImageState, Images, Theme};
+
+pub(crate) const GARBAGE_FONT_SIZE: f32 = 24.0;
+
+const NO_EFFECTS: TextEffects = TextEffects {
+    outline: None,
+    shadow: None,
+    backdrop: None,
+};
+
+// How far rising parts of a build travel, in canvas units.
+const RISE_DISTANCE: f32 = 48.0;
+
+/// How far a slide's build has got.
+#[derive(Clone, Copy)]
+pub(crate) struct BuildState {
+    /// Parts shown, counting the one entering.
+    pub revealed: usize,
+    /// Progress of the last part's entry effect, from 0 to 1.
+    pub entering: f32,
+}
+
+impl BuildState {
+    /// Which lines of a layer taking up `parts` are shown. Layers taking
+    /// up a single part are shown whole.
+    fn reveal(&self, parts: Range<usize>) -> Reveal {
+        let lines = |range: Range<usize>| {
+            if parts.len() > 1 {
+                range
+            } else if range.is_empty() {
+                0..0
+            } else {
+                0..usize::MAX
+            }
+        };
+        let revealed = self.revealed.clamp(parts.start, parts.end) - parts.start;
+        if self.entering < 1.0 && parts.contains(&self.revealed.saturating_sub(1)) {
+            Reveal {
+                shown: lines(0..revealed - 1),
+                entering: Some((lines(revealed - 1..revealed), self.entering)),
+            }
+        } else {
+            Reveal {
+                shown: lines(0..revealed),
+                entering: None,
+            }
+        }
+    }
+}
+
+/// Paints a layer that's already been placed.
+type Paint<'a> = Box<dyn FnOnce() + 'a>;
+
+/// The lines of a layer a build shows.
+struct Reveal {
+    shown: Range<usize>,
+    /// Lines entering, and how far along they are from 0 to 1.
+    entering: Option<(Range<usize>, f32)>,
+}
+
+impl Reveal {
+    const ALL: Reveal = Reveal {
+        shown: 0..usize::MAX,
+        entering: None,
+    };
+
+    const HIDDEN: Reveal = Reveal {
+        shown: 0..0,
+        entering: None,
+    };
+
+    fn is_hidden(&self) -> bool {
+        self.shown.is_empty() && self.entering.is_none()
+    }
+
+    /// Opacity and offset of the `index`th line, item or row, if it's shown.
+    fn line(&self, index: usize, effect: BuildEffect, scale: f32) -> Option<(f32, Vec2)> {
+        if self.shown.contains(&index) {
+            return Some((1.0, Vec2::ZERO));
+        }
+        match &self.entering {
+            Some((lines, t)) if lines.contains(&index) => Some((*t, rise(effect, *t) * scale)),
+            _ => None,
+        }
+    }
+
+    /// Opacity and offset of the layer as a whole.
+    fn block(&self, effect: BuildEffect, scale: f32) -> (f32, Vec2) {
+        match self.entering {
+            Some((_, t)) if self.shown.is_empty() => (t, rise(effect, t) * scale),
+            _ => (1.0, Vec2::ZERO),
+        }
+    }
+}
+
+fn rise(effect: BuildEffect, t: f32) -> Vec2 {
+    match effect {
+        BuildEffect::Fade => Vec2::ZERO,
+        BuildEffect::Rise => vec2(0.0, (1.0 - t) * RISE_DISTANCE),
+    }
+}
+
+/// Draws slides into a frame. Everything is clipped to `clip`, so a frame
+/// moved by a transition doesn't spill out of the canvas.
+pub(crate) struct SlidePainter<'a> {
+    pub ui: &'a Ui,
+    pub images: &'a Images,
+    pub theme: &'a Theme,
+    pub frame: Frame,
+    pub clip: Rect,
+    pub clock: Clock,
+    pub meta: &'a Meta,
+    pub placeholders: Placeholders<'a>,
+}
+
+impl SlidePainter<'_> {
+    fn painter(&self) -> Painter {
+        self.ui.painter_at(self.clip)
+    }
+
+    pub fn background(&self, slide: &Slide, opacity: f32) {
+        if let Some(background) = slide.layers.background().or(self.theme.background()) {
+            self.draw_background(background, opacity);
+        }
+    }
+
+    /// The theme's logo, shown while the output is cleared to it.
+    pub fn logo(&self, opacity: f32) {
+        let Some(logo) = self.theme.logo() else {
+            return;
+        };
+        let mut layout = Layout::from(self.frame, self.frame.canvas());
+        if let Some(paint) = self.place_image(&mut layout, logo, opacity, Reveal::ALL, BuildEffect::default()) {
+            paint();
+        }
+    }
+
+    pub fn layers(&self, slide: &Slide, font_sizes: &FontSizes, slide_index: usize, opacity: f32, build: BuildState) {
+        let effect = slide.build_effect;
+        let mut paints = Vec::new();
+
+        // The theme's decorations are laid out on their own, and go below
+        // slide layers with the same z.
+        let mut layout = Layout::from(self.frame, self.frame.canvas());
+        for layer in self.theme.decorations() {
+            let composite = layer.composite();
+            let reveal = if composite.visible { Reveal::ALL } else { Reveal::HIDDEN };
+            let font_size = |font_size: FontSize| match font_size {
+                FontSize::Fixed(size) => size as f32,
+                FontSize::Auto { max, .. } => max as f32,
+            };
+            let paint = self.place(&mut layout, layer, font_size, composite.opacity * opacity, reveal, effect);
+            paints.extend(paint.map(|paint| (composite.z, paint)));
+        }
+
+        let mut layout = Layout::from(self.frame, self.frame.canvas());
+        let parts = slide.build_parts();
+        for (index, layer) in slide.layers.view().iter().enumerate() {
+            let composite = layer.composite();
+            let reveal = if composite.visible { build.reveal(parts[index].clone()) } else { Reveal::HIDDEN };
+            let font_size = |font_size: FontSize| match font_size {
+                FontSize::Fixed(size) => size as f32,
+                FontSize::Auto { max, .. } => font_sizes.get(slide_index, index).unwrap_or(max as f32),
+            };
+            let paint = self.place(&mut layout, layer, font_size, composite.opacity * opacity, reveal, effect);
+            paints.extend(paint.map(|paint| (composite.z, paint)));
+        }
+
+        // The footer goes over everything, and isn't part of any build.
+        paints.extend(self.place_footer(slide_index, opacity).map(|paint| (i32::MAX, paint)));
+
+        // Layers are placed in source order, so stacked layers keep their
+        // spots, but painted by z. The sort is stable, so equal z keeps
+        // source order.
+        paints.sort_by_key(|(z, _)| *z);
+        for (_, paint) in paints {
+            paint();
+        }
+    }
+
+    fn place_footer(&self, slide_index: usize, opacity: f32) -> Option<Paint<'_>> {
+        let footer = self.theme.footer()?;
+        if !footer.is_on(slide_index, self.placeholders.slide_count) {
+            return None;
+        }
+        let text = footer.text(self.meta)?;
+        let scale = self.frame.scale;
+        let text_color = footer.color.map_or(self.ui.visuals().text_color(), color);
+        let wrap_width = (self.frame.canvas().x - 2.0 * footer.margin).max(footer.font_size) * scale;
+        let galley = self
+            .ui
+            .fonts(|fonts| layout_text(fonts, &text, (footer.font_size * scale).max(1.0), text_color, wrap_width));
+        let bottom = self.frame.rect.bottom() - footer.margin * scale;
+        let pos = pos2(self.frame.rect.center().x, bottom - galley.size().y);
+        Some(Box::new(move || {
+            self.painter().galley_with_color(pos, galley, text_color.linear_multiply(opacity));
+        }))
+    }
+
+    fn place<'a>(
+        &'a self,
+        layout: &mut Layout,
+        layer: &'a Layer,
+        font_size: impl Fn(FontSize) -> f32,
+        opacity: f32,
+        reveal: Reveal,
+        effect: BuildEffect,
+    ) -> Option<Paint<'a>> {
+        let text_color = self.ui.visuals().text_color();
+        match layer {
+            Layer::Background(_) => None,
+            Layer::Image(layer) => self.place_image(layout, layer, opacity, reveal, effect),
+            Layer::List(layer) => self.place_list(layout, layer, opacity, reveal, effect),
+            Layer::Table(layer) => self.place_table(layout, layer, opacity, reveal, effect),
+            Layer::Shape(layer) => self.place_shape(layout, layer, opacity, reveal, effect),
+            Layer::Qr(layer) => self.place_qr(layout, layer, opacity, reveal, effect),
+            Layer::Timer(layer) => self.place_timer(layout, layer, text_color, opacity, reveal, effect),
+            Layer::Title(layer) => {
+                let text = Text {
+                    text: self.placeholders.resolve(layer.text),
+                    font_size: font_size(layer.font_size),
+                    color: text_color,
+                    effects: &layer.effects,
+                };
+                self.place_text(layout, &layer.geometry, text, opacity, reveal, effect)
+            }
+            Layer::Garbage(node) => {
+                let text = Text {
+                    text: node.text().into(),
+                    font_size: GARBAGE_FONT_SIZE,
+                    color: Color32::RED,
+                    effects: &NO_EFFECTS,
+                };
+                self.place_text(layout, &Geometry::default(), text, opacity, reveal, effect)
+            }
+            Layer::GarbageNode(node) => {
+                let text = Text {
+                    text: node.text().into(),
+                    font_size: GARBAGE_FONT_SIZE,
+                    color: Color32::RED,
+                    effects: &NO_EFFECTS,
+                };
+                self.place_text(layout, &Geometry::default(), text, opacity, reveal, effect)
+            }
+            Layer::Text(layer) => {
+                let text = Text {
+                    text: self.placeholders.resolve(layer.text),
+                    font_size: font_size(layer.font_size),
+                    color: text_color,
+                    effects: &layer.effects,
+                };
+                self.place_text(layout, &layer.geometry, text, opacity, reveal, effect)
+            }
+        }
+    }
+
+    fn place_text<'a>(
+        &'a self,
+        layout: &mut Layout,
+        geometry: &Geometry,
+        text: Text<'a>,
+        opacity: f32,
+        reveal: Reveal,
+        effect: BuildEffect,
+    ) -> Option<Paint<'a>> {
+        let ui = self.ui;
+        let scale = self.frame.scale;
+        // egui can't lay out text under a pixel, which tiny previews ask for.
+        let font_size = (text.font_size * scale).max(1.0);
+        let mut galley = None;
+        let rect = layout.place(geometry, |width| {
+            let laid_out = ui.fonts(|fonts| layout_text(fonts, &text.text, font_size, text.color, width * scale));
+            let height = laid_out.size().y / scale;
+            galley = Some(laid_out);
+            height
+        });
+        let galley = galley
+            .unwrap_or_else(|| ui.fonts(|fonts| layout_text(fonts, &text.text, font_size, text.color, rect.width())));
+
+        if reveal.is_hidden() {
+            return None;
+        }
+        Some(Box::new(move || {
+            let painter = self.painter();
+            let pos = pos2(rect.center().x, rect.top());
+            if let Some(backdrop) = text.effects.backdrop {
+                let (alpha, offset) = reveal.block(effect, scale);
+                let bounds = galley.rect.translate(pos.to_vec2() + offset).expand(backdrop.padding * scale);
+                let backdrop_color = color(backdrop.color).linear_multiply(alpha * opacity);
+                painter.rect_filled(bounds, backdrop.radius * scale, backdrop_color);
+            }
+            if !reveal.shown.is_empty() {
+                self.paint_text(&painter, pos, only_lines(&galley, reveal.shown.clone()), &text, opacity);
+            }
+            if let Some((lines, t)) = reveal.entering {
+                let pos = pos + rise(effect, t) * scale;
+                self.paint_text(&painter, pos, only_lines(&galley, lines), &text, t * opacity);
+            }
+        }))
+    }
+
+    fn place_timer<'a>(
+        &'a self,
+        layout: &mut Layout,
+        layer: &'a TimerLayer,
+        color: Color32,
+        opacity: f32,
+        reveal: Reveal,
+        effect: BuildEffect,
+    ) -> Option<Paint<'a>> {
+        // Hidden countdowns keep their spot, like hidden layers.
+        let (text, reveal) = match self.clock.text(layer) {
+            Some(text) => (text, reveal),
+            None => (String::new(), Reveal::HIDDEN),
+        };
+        let text = Text {
+            text: text.into(),
+            font_size: layer.font_size,
+            color,
+            effects: &layer.effects,
+        };
+        self.place_text(layout, &layer.geometry, text, opacity, reveal, effect)
+    }
+
+    fn place_list<'a>(
+        &'a self,
+        layout: &mut Layout,
+        layer: &ListLayer,
+        opacity: f32,
+        reveal: Reveal,
+        effect: BuildEffect,
+    ) -> Option<Paint<'a>> {
+        let ui = self.ui;
+        let scale = self.frame.scale;
+        let style = layer.style.or(self.theme.list_style());
+        let text_color = ui.visuals().text_color();
+        let mut laid_out = None;
+        let rect = layout.place(&layer.geometry, |width| {
+            let list = ui.fonts(|fonts| layout_list(fonts, layer, style, width, scale, text_color));
+            let height = list.size.y / scale;
+            laid_out = Some(list);
+            height
+        });
+        let list = laid_out.unwrap_or_else(|| {
+            ui.fonts(|fonts| layout_list(fonts, layer, style, rect.width() / scale, scale, text_color))
+        });
+
+        if reveal.is_hidden() {
+            return None;
+        }
+        Some(Box::new(move || {
+            let painter = self.painter();
+            for (index, row) in list.rows.into_iter().enumerate() {
+                let Some((alpha, offset)) = reveal.line(index, effect, scale) else {
+                    continue;
+                };
+                let origin = rect.min + offset;
+                let marker_color = row.marker_color.linear_multiply(alpha * opacity);
+                painter.galley_with_color(origin + row.marker_pos, row.marker, marker_color);
+                let text_color = row.text_color.linear_multiply(alpha * opacity);
+                painter.galley_with_color(origin + row.text_pos, row.text, text_color);
+            }
+        }))
+    }
+
+    fn place_table<'a>(
+        &'a self,
+        layout: &mut Layout,
+        layer: &TableLayer,
+        opacity: f32,
+        reveal: Reveal,
+        effect: BuildEffect,
+    ) -> Option<Paint<'a>> {
+        let ui = self.ui;
+        let scale = self.frame.scale;
+        let style = layer.style.or(self.theme.table_style());
+        let text_color = ui.visuals().text_color();
+        let mut laid_out = None;
+        let rect = layout.place(&layer.geometry, |width| {
+            let table = ui.fonts(|fonts| layout_table(fonts, layer, style, width, scale, text_color));
+            let height = table.size.y / scale;
+            laid_out = Some(table);
+            height
+        });
+        let table = laid_out.unwrap_or_else(|| {
+            ui.fonts(|fonts| layout_table(fonts, layer, style, rect.width() / scale, scale, text_color))
+        });
+
+        if reveal.is_hidden() {
+            return None;
+        }
+        Some(Box::new(move || {
+            let painter = self.painter();
+            let last = table.rows.len().saturating_sub(1);
+            for (index, row) in table.rows.into_iter().enumerate() {
+                let Some((alpha, offset)) = reveal.line(index, effect, scale) else {
+                    continue;
+                };
+                let opacity = alpha * opacity;
+                let row_rect = row.rect.translate(rect.min.to_vec2() + offset);
+                if row.is_header {
+                    painter.rect_filled(row_rect, 0.0, color(style.header_background()).linear_multiply(opacity));
+                }
+                if index < last {
+                    let border = Stroke::new(2.0 * scale, color(style.border_color()).linear_multiply(opacity));
+                    painter.hline(row_rect.left()..=row_rect.right(), row_rect.bottom(), border);
+                }
+                let text_color = row.color.linear_multiply(opacity);
+                for cell in row.cells {
+                    painter.galley_with_color(rect.min + offset + cell.pos, cell.galley, text_color);
+                }
+            }
+        }))
+    }
+
+    fn paint_text(&self, painter: &Painter, pos: Pos2, galley: Arc<Galley>, text: &Text, opacity: f32) {
+        let scale = self.frame.scale;
+        let effects = text.effects;
+        // There's no blur or stroke for text in egui, so both are faked by
+        // drawing the text several times around where it would go.
+        if let Some(shadow) = effects.shadow {
+            let offset = vec2(shadow.x, shadow.y) * scale;
+            let blur = shadow.blur * scale;
+            let (copies, alpha) = if blur > 0.0 { (9, 2.0 / 9.0) } else { (1, 1.0) };
+            let shadow_color = color(shadow.color).linear_multiply(alpha * opacity);
+            for i in 0..copies {
+                // The first copy sits in the middle, the rest spread around it.
+                let spread = if i == 0 { Vec2::ZERO } else { Vec2::angled(i as f32 / 8.0 * TAU) * blur / 2.0 };
+                painter.galley_with_color(pos + offset + spread, galley.clone(), shadow_color);
+            }
+        }
+        if let Some(outline) = effects.outline {
+            let width = outline.width * scale;
+            let steps = if width > 2.0 { 16 } else { 8 };
+            let outline_color = color(outline.color).linear_multiply(opacity);
+            for i in 0..steps {
+                let offset = Vec2::angled(i as f32 / steps as f32 * TAU) * width;
+                painter.galley_with_color(pos + offset, galley.clone(), outline_color);
+            }
+        }
+        painter.galley_with_color(pos, galley, text.color.linear_multiply(opacity));
+    }
+
+    fn draw_background(&self, layer: &BackgroundLayer, opacity: f32) {
+        let painter = self.painter();
+        let rect = self.frame.rect;
+        let opacity = layer.composite.opacity * opacity;
+        match layer.background {
+            Background::Color(rgba) => {
+                painter.rect_filled(rect, 0.0, color(rgba).linear_multiply(opacity));
+            }
+            Background::LinearGradient { from, to, angle } => {
+                // The gradient is affine in position, so interpolating between
+                // the corners of a single quad is exact.
+                let direction = Vec2::angled(angle.to_radians());
+                let extent = rect.width() * direction.x.abs() + rect.height() * direction.y.abs();
+                let mut mesh = Mesh::default();
+                for corner in [rect.left_top(), rect.right_top(), rect.right_bottom(), rect.left_bottom()] {
+                    let t = 0.5 + (corner - rect.center()).dot(direction) / extent;
+                    mesh.colored_vertex(corner, mix(from, to, t).linear_multiply(opacity));
+                }
+                mesh.add_triangle(0, 1, 2);
+                mesh.add_triangle(0, 2, 3);
+                painter.add(mesh);
+            }
+            Background::RadialGradient { from, to } => {
+                const SEGMENTS: u32 = 64;
+                let radius = rect.size().length() / 2.0;
+                let mut mesh = Mesh::default();
+                mesh.colored_vertex(rect.center(), color(from).linear_multiply(opacity));
+                for i in 0..SEGMENTS {
+                    let corner: Pos2 = rect.center() + Vec2::angled(i as f32 / SEGMENTS as f32 * TAU) * radius;
+                    mesh.colored_vertex(corner, color(to).linear_multiply(opacity));
+                    mesh.add_triangle(0, 1 + i, 1 + (i + 1) % SEGMENTS);
+                }
+                painter.add(mesh);
+            }
+            Background::Image { src, fit } => {
+                self.paint_image(src, fit, rect, opacity);
+            }
+        }
+    }
+
+    fn place_image<'a>(
+        &'a self,
+        layout: &mut Layout,
+        layer: &'a ImageLayer,
+        opacity: f32,
+        reveal: Reveal,
+        effect: BuildEffect,
+    ) -> Option<Paint<'a>> {
+        // Images cover the whole canvas unless told otherwise, so they never
+        // push other layers down.
+        let geometry = Geometry {
+            x: layer.geometry.x.or(Some(Length::Units(0.0))),
+            y: layer.geometry.y.or(Some(Length::Units(0.0))),
+            height: layer.geometry.height.or(Some(Length::Percent(100.0))),
+            ..layer.geometry
+        };
+        let target = layout.place(&geometry, |_| 0.0);
+        if reveal.is_hidden() {
+            return None;
+        }
+        let (alpha, offset) = reveal.block(effect, self.frame.scale);
+        Some(Box::new(move || {
+            self.paint_image(layer.src, layer.fit, target.translate(offset), alpha * opacity);
+        }))
+    }
+
+    fn place_shape<'a>(
+        &'a self,
+        layout: &mut Layout,
+        layer: &'a ShapeLayer,
+        opacity: f32,
+        reveal: Reveal,
+        effect: BuildEffect,
+    ) -> Option<Paint<'a>> {
+        let rect = layout.place(&layer.geometry, |_| layer.natural_height());
+        if reveal.is_hidden() {
+            return None;
+        }
+        let (alpha, offset) = reveal.block(effect, self.frame.scale);
+        Some(Box::new(move || self.paint_shape(layer, rect.translate(offset), alpha * opacity)))
+    }
+
+    fn paint_shape(&self, layer: &ShapeLayer, rect: Rect, opacity: f32) {
+        let painter = self.painter();
+        let scale = self.frame.scale;
+        let fill = layer.fill.map_or(Color32::TRANSPARENT, |fill| color(fill).linear_multiply(opacity));
+        let stroke = layer.stroke.map_or(Stroke::NONE, |stroke| {
+            Stroke::new(layer.stroke_width * scale, color(stroke).linear_multiply(opacity))
+        });
+        match layer.kind {
+            ShapeKind::Rect => {
+                painter.rect(rect, 0.0, fill, stroke);
+            }
+            ShapeKind::Rounded => {
+                painter.rect(rect, layer.radius * scale, fill, stroke);
+            }
+            ShapeKind::Ellipse => {
+                const SEGMENTS: usize = 64;
+                let radius = rect.size() / 2.0;
+                let points = (0..SEGMENTS)
+                    .map(|i| {
+                        let direction = Vec2::angled(i as f32 / SEGMENTS as f32 * TAU);
+                        rect.center() + vec2(direction.x * radius.x, direction.y * radius.y)
+                    })
+                    .collect();
+                painter.add(Shape::convex_polygon(points, fill, stroke));
+            }
+            ShapeKind::Line => {
+                let line_color = color(layer.line_color()).linear_multiply(opacity);
+                let stroke = Stroke::new(layer.stroke_width * scale, line_color);
+                let points = if rect.width() >= rect.height() {
+                    [rect.left_center(), rect.right_center()]
+                } else {
+                    [rect.center_top(), rect.center_bottom()]
+                };
+                painter.line_segment(points, stroke);
+            }
+        }
+    }
+
+    fn place_qr<'a>(
+        &'a self,
+        layout: &mut Layout,
+        layer: &'a QrLayer,
+        opacity: f32,
+        reveal: Reveal,
+        effect: BuildEffect,
+    ) -> Option<Paint<'a>> {
+        // Codes are square unless given a height.
+        let rect = layout.place(&layer.geometry, |width| width);
+        if reveal.is_hidden() {
+            return None;
+        }
+        let (alpha, offset) = reveal.block(effect, self.frame.scale);
+        Some(Box::new(move || self.paint_qr(layer, rect.translate(offset), alpha * opacity)))
+    }
+
+    /// Paints the modules as one mesh of axis aligned quads, merging runs of
+    /// dark modules along each row, so codes stay sharp at any size.
+    fn paint_qr(&self, layer: &QrLayer, rect: Rect, opacity: f32) {
+        let painter = self.painter();
+        let Some(modules) = &layer.modules else {
+            let font = FontId::proportional((GARBAGE_FONT_SIZE * self.frame.scale).max(1.0));
+            let message = "QR code payload is too long";
+            painter.text(rect.left_top(), Align2::LEFT_TOP, message, font, Color32::RED);
+            return;
+        };
+        let square = Rect::from_center_size(rect.center(), Vec2::splat(rect.width().min(rect.height())));
+        painter.rect_filled(square, 0.0, color(layer.light).linear_multiply(opacity));
+
+        let module = square.width() / layer.side() as f32;
+        let origin = square.min + Vec2::splat(layer.quiet_zone as f32 * module);
+        let dark = color(layer.dark).linear_multiply(opacity);
+        let mut mesh = Mesh::default();
+        for y in 0..modules.size {
+            let mut x = 0;
+            while x < modules.size {
+                if !modules.is_dark(x, y) {
+                    x += 1;
+                    continue;
+                }
+                let start = x;
+                while x < modules.size && modules.is_dark(x, y) {
+                    x += 1;
+                }
+                let min = origin + vec2(start as f32, y as f32) * module;
+                mesh.add_colored_rect(Rect::from_min_size(min, vec2((x - start) as f32, 1.0) * module), dark);
+            }
+        }
+        painter.add(mesh);
+    }
+
+    fn paint_image(&self, src: &str, fit: Fit, target: Rect, opacity: f32) {
+        match self.images.get(self.ui.ctx(), src) {
+            ImageState::Loading => {}
+            ImageState::Ready(texture) => {
+                let placed = fit_image(fit, texture.size_vec2(), target);
+                let visible = placed.intersect(target);
+                if !visible.is_positive() {
+                    return;
+                }
+                let uv = Rect::from_min_max(
+                    ((visible.min - placed.min) / placed.size()).to_pos2(),
+                    ((visible.max - placed.min) / placed.size()).to_pos2(),
+                );
+                let tint = Color32::WHITE.linear_multiply(opacity);
+                self.painter().image(texture.id(), visible, uv, tint);
+            }
+            ImageState::Failed(message) => {
+                let font = FontId::proportional(14.0);
+                self.painter().text(target.left_top(), Align2::LEFT_TOP, message, font, Color32::RED);
+            }
+        }
+    }
+}
+
+struct Text<'a> {
+    text: Cow<'a, str>,
+    font_size: f32,
+    color: Color32,
+    effects: &'a TextEffects,
+}
+
+pub(crate) fn color(rgba: u32) -> Color32 {
+    let [r, g, b, a] = rgba.to_be_bytes();
+    Color32::from_rgba_unmultiplied(r, g, b, a)
+}
+
+fn mix(from: u32, to: u32, t: f32) -> Color32 {
+    let t = t.clamp(0.0, 1.0);
+    Color32::from(Rgba::from(color(from)) * (1.0 - t) + Rgba::from(color(to)) * t)
+}
+
+/// A copy of `galley` showing only the given source lines.
+fn only_lines(galley: &Arc<Galley>, lines: Range<usize>) -> Arc<Galley> {
+    if lines == (0..usize::MAX) {
+        return galley.clone();
+    }
+    let mut res = Galley::clone(galley);
+    let mut line = 0;
+    for row in &mut res.rows {
+        if !lines.contains(&line) {
+            row.visuals.mesh = Mesh::default();
+            row.visuals.glyph_vertex_range = 0..0;
+        }
+        if row.ends_with_newline {
+            line += 1;
+        }
+    }
+    Arc::new(res)
+}
+
+fn fit_image(fit: Fit, image: Vec2, target: Rect) -> Rect {
+    let size = match fit {
+        Fit::Fill => return target,
+        Fit::Contain => image * (target.width() / image.x).min(target.height() / image.y),
+        Fit::Cover => image * (target.width() / image.x).max(target.height() / image.y),
+        Fit::None => image,
+    };
+    Rect::from_center_size(target.center(), size)
+}
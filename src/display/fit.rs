@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+
+use egui::text::Fonts;
+use egui::{Color32, Vec2};
+use slide::{Deck, FontSize, Geometry, Layer};
+
+use crate::layout::{layout_text, Frame, Layout};
+use crate::draw::GARBAGE_FONT_SIZE;
+use crate::list::layout_list;
+use crate::table::layout_table;
+use crate::Theme;
+
+/// Font sizes picked for `font_size = "auto"` layers, by slide and layer
+/// index. Measured on the unscaled canvas, so every display showing the
+/// deck picks the same sizes.
+#[derive(Default)]
+pub(crate) struct FontSizes(HashMap<(usize, usize), f32>);
+
+impl FontSizes {
+    pub fn get(&self, slide: usize, layer: usize) -> Option<f32> {
+        self.0.get(&(slide, layer)).copied()
+    }
+
+    pub fn fit(fonts: &Fonts, deck: &Deck, canvas: Vec2, theme: &Theme) -> Self {
+        let mut sizes = HashMap::new();
+        let mut groups: HashMap<&str, f32> = HashMap::new();
+        let mut members = Vec::new();
+
+        let garbage = Geometry::default();
+        let garbage_size = FontSize::Fixed(GARBAGE_FONT_SIZE as f64);
+        for (slide_index, slide) in deck.view().iter().enumerate() {
+            let mut layout = Layout::from(Frame::unscaled(canvas), canvas);
+            for (layer_index, layer) in slide.layers.view().iter().enumerate() {
+                let (text, font_size, geometry, fit_group) = match layer {
+                    Layer::Text(layer) => (layer.text, layer.font_size, &layer.geometry, layer.fit_group),
+                    Layer::Title(layer) => (layer.text, layer.font_size, &layer.geometry, layer.fit_group),
+                    Layer::Garbage(node) => (node.text(), garbage_size, &garbage, None),
+                    Layer::GarbageNode(node) => (node.text(), garbage_size, &garbage, None),
+                    Layer::List(list) => {
+                        let style = list.style.or(theme.list_style());
+                        layout.place(&list.geometry, |width| {
+                            layout_list(fonts, list, style, width, 1.0, Color32::WHITE).size.y
+                        });
+                        continue;
+                    }
+                    Layer::Table(table) => {
+                        let style = table.style.or(theme.table_style());
+                        layout.place(&table.geometry, |width| {
+                            layout_table(fonts, table, style, width, 1.0, Color32::WHITE).size.y
+                        });
+                        continue;
+                    }
+                    Layer::Shape(shape) => {
+                        layout.place(&shape.geometry, |_| shape.natural_height());
+                        continue;
+                    }
+                    Layer::Timer(timer) => {
+                        // Timers change every frame, so they're measured
+                        // showing a typical time.
+                        layout.place(&timer.geometry, |width| measure(fonts, "00:00", timer.font_size, width).y);
+                        continue;
+                    }
+                    Layer::Qr(qr) => {
+                        layout.place(&qr.geometry, |width| width);
+                        continue;
+                    }
+                    Layer::Image(_) | Layer::Background(_) => continue,
+                };
+                let size = match font_size {
+                    FontSize::Fixed(size) => size as f32,
+                    FontSize::Auto { min, max } => {
+                        let size = largest_fitting(fonts, text, min as f32, max as f32, layout.available(geometry));
+                        sizes.insert((slide_index, layer_index), size);
+                        if let Some(group) = fit_group {
+                            let smallest = groups.entry(group).or_insert(size);
+                            *smallest = smallest.min(size);
+                            members.push((group, (slide_index, layer_index)));
+                        }
+                        size
+                    }
+                };
+                layout.place(geometry, |width| measure(fonts, text, size, width).y);
+            }
+        }
+
+        for (group, member) in members {
+            sizes.insert(member, groups[group]);
+        }
+        FontSizes(sizes)
+    }
+}
+
+fn measure(fonts: &Fonts, text: &str, font_size: f32, width: f32) -> Vec2 {
+    layout_text(fonts, text, font_size, Color32::WHITE, width).size()
+}
+
+/// Binary searches whole sizes in `min..=max` for the largest one where
+/// `text` fits in `available`, falling back to `min`.
+fn largest_fitting(fonts: &Fonts, text: &str, min: f32, max: f32, available: Vec2) -> f32 {
+    let fits = |size: f32| {
+        let size = measure(fonts, text, size, available.x);
+        size.x <= available.x && size.y <= available.y
+    };
+
+    let (mut low, mut high) = (min.floor(), max.floor());
+    if fits(high) {
+        return high;
+    }
+    while high - low > 1.0 {
+        let middle = ((low + high) / 2.0).floor();
+        if fits(middle) {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    low
+}
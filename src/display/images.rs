@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+use egui::{ColorImage, Context, TextureHandle, TextureOptions};
+
+// Larger images are downscaled before upload, so a single photo can't
+// exceed the GPU texture limit or stall the frame it's uploaded in.
+const MAX_TEXTURE_SIDE: u32 = 4096;
+
+// How often a failed image's file is checked for changes.
+const RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+pub enum ImageState {
+    Loading,
+    Ready(TextureHandle),
+    Failed(String),
+}
+
+enum Entry {
+    /// With when the file was last modified as decoding started.
+    Loading(Option<SystemTime>),
+    Ready(TextureHandle),
+    /// Tried again once the file is modified, like when it was still being
+    /// written.
+    Failed {
+        message: String,
+        modified: Option<SystemTime>,
+        checked: Instant,
+    },
+}
+
+struct Store {
+    root: PathBuf,
+    entries: HashMap<String, Entry>,
+    sender: Sender<(String, Result<ColorImage, String>)>,
+    receiver: Receiver<(String, Result<ColorImage, String>)>,
+}
+
+/// Images referenced by slides, decoded on worker threads and uploaded as
+/// textures once ready. Cheap to clone; clones share the same cache.
+#[derive(Clone)]
+pub struct Images(Arc<Mutex<Store>>);
+
+impl Default for Images {
+    fn default() -> Self {
+        Self::from(".")
+    }
+}
+
+impl Images {
+    pub fn from(root: &str) -> Self {
+        let (sender, receiver) = channel();
+        Self(Arc::new(Mutex::new(Store {
+            root: PathBuf::from(root),
+            entries: HashMap::new(),
+            sender,
+            receiver,
+        })))
+    }
+
+    pub fn get(&self, ctx: &Context, src: &str) -> ImageState {
+        let mut store = self.0.lock().unwrap();
+
+        // Take at most one decoded image per lookup, so a frame showing
+        // many images spreads their uploads over the frames after it.
+        if let Ok((src, image)) = store.receiver.try_recv() {
+            let entry = match image {
+                Ok(image) => Entry::Ready(ctx.load_texture(src.as_str(), image, TextureOptions::LINEAR)),
+                Err(message) => Entry::Failed {
+                    message,
+                    modified: match store.entries.get(&src) {
+                        Some(Entry::Loading(modified)) => *modified,
+                        _ => None,
+                    },
+                    checked: Instant::now(),
+                },
+            };
+            store.entries.insert(src, entry);
+        }
+
+        let path = store.root.join(src);
+        let load = match store.entries.get_mut(src) {
+            None => true,
+            Some(Entry::Failed { modified, checked, .. }) if checked.elapsed() >= RETRY_INTERVAL => {
+                *checked = Instant::now();
+                modified_at(&path) != *modified
+            }
+            Some(_) => false,
+        };
+        if load {
+            let sender = store.sender.clone();
+            let key = src.to_owned();
+            let modified = modified_at(&path);
+            std::thread::spawn(move || {
+                let _ = sender.send((key, decode(path)));
+            });
+            store.entries.insert(src.to_owned(), Entry::Loading(modified));
+        }
+
+        match &store.entries[src] {
+            Entry::Loading(_) => {
+                ctx.request_repaint();
+                ImageState::Loading
+            }
+            Entry::Ready(texture) => ImageState::Ready(texture.clone()),
+            Entry::Failed { message, .. } => {
+                ctx.request_repaint_after(RETRY_INTERVAL);
+                ImageState::Failed(message.clone())
+            }
+        }
+    }
+}
+
+fn modified_at(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+fn decode(path: PathBuf) -> Result<ColorImage, String> {
+    let image = image::open(&path).map_err(|error| format!("{}: {}", path.display(), error))?;
+    let image = if image.width() > MAX_TEXTURE_SIDE || image.height() > MAX_TEXTURE_SIDE {
+        image.resize(MAX_TEXTURE_SIDE, MAX_TEXTURE_SIDE, image::imageops::FilterType::Triangle)
+    } else {
+        image
+    };
+    let image = image.to_rgba8();
+    let size = [image.width() as usize, image.height() as usize];
+    Ok(ColorImage::from_rgba_unmultiplied(size, image.as_raw()))
+}
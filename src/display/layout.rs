@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use egui::text::{Fonts, Galley, LayoutJob};
+use egui::{vec2, Align, Color32, FontId, Rect, Vec2};
+use slide::Geometry;
+
+// Vertical gap between stacked layers, in canvas units.
+const LAYER_SPACING: f32 = 16.0;
+
+/// Where the virtual canvas ends up on screen, and how much it's scaled.
+#[derive(Clone, Copy)]
+pub(crate) struct Frame {
+    pub rect: Rect,
+    pub scale: f32,
+}
+
+impl Frame {
+    pub fn fit(canvas: Vec2, rect: Rect) -> Self {
+        let scale = (rect.width() / canvas.x).min(rect.height() / canvas.y);
+        Self {
+            rect: Rect::from_center_size(rect.center(), canvas * scale),
+            scale,
+        }
+    }
+
+    /// The canvas itself, one point per canvas unit.
+    pub fn unscaled(canvas: Vec2) -> Self {
+        Self {
+            rect: Rect::from_min_size(egui::Pos2::ZERO, canvas),
+            scale: 1.0,
+        }
+    }
+
+    /// Size of the canvas, in canvas units.
+    pub fn canvas(&self) -> Vec2 {
+        self.rect.size() / self.scale
+    }
+
+    /// The same frame moved by `offset` canvas units.
+    pub fn translate(&self, offset: Vec2) -> Self {
+        Self {
+            rect: self.rect.translate(offset * self.scale),
+            scale: self.scale,
+        }
+    }
+
+    pub fn rect(&self, min: Vec2, size: Vec2) -> Rect {
+        Rect::from_min_size(self.rect.min + min * self.scale, size * self.scale)
+    }
+}
+
+/// Places layer boxes on the canvas, stacking unpositioned layers from the
+/// top down.
+pub(crate) struct Layout {
+    pub frame: Frame,
+    canvas: Vec2,
+    y: f32,
+}
+
+impl Layout {
+    pub fn from(frame: Frame, canvas: Vec2) -> Self {
+        Self {
+            frame,
+            canvas,
+            y: 0.0,
+        }
+    }
+
+    fn inset(&self, geometry: &Geometry) -> f32 {
+        geometry.margin.resolve(self.canvas.x) + geometry.padding.resolve(self.canvas.x)
+    }
+
+    fn width(&self, geometry: &Geometry) -> f32 {
+        geometry.width.map_or(self.canvas.x, |width| width.resolve(self.canvas.x))
+    }
+
+    /// The most room the layer's content could get, in canvas units.
+    pub fn available(&self, geometry: &Geometry) -> Vec2 {
+        let canvas = self.canvas;
+        let height = match geometry.height {
+            Some(height) => height.resolve(canvas.y),
+            None if geometry.is_positioned() => canvas.y - geometry.y.map_or(0.0, |y| y.resolve(canvas.y)),
+            None => canvas.y - self.y,
+        };
+        vec2(self.width(geometry), height) - Vec2::splat(2.0 * self.inset(geometry))
+    }
+
+    /// Returns the screen rect inside the layer's margin and padding.
+    /// `content_height` gets the width available to the content and returns
+    /// its height, both in canvas units.
+    pub fn place(&mut self, geometry: &Geometry, content_height: impl FnOnce(f32) -> f32) -> Rect {
+        let canvas = self.canvas;
+        let inset = self.inset(geometry);
+        let width = self.width(geometry);
+        let height = match geometry.height {
+            Some(height) => height.resolve(canvas.y),
+            None => content_height(width - 2.0 * inset) + 2.0 * inset,
+        };
+        let min = if geometry.is_positioned() {
+            let [x, y] = geometry.anchor.fraction();
+            vec2(
+                geometry.x.map_or(0.0, |length| length.resolve(canvas.x)) - x * width,
+                geometry.y.map_or(0.0, |length| length.resolve(canvas.y)) - y * height,
+            )
+        } else {
+            let min = vec2((canvas.x - width) / 2.0, self.y);
+            self.y += height + LAYER_SPACING;
+            min
+        };
+        self.frame.rect(min, vec2(width, height)).shrink(inset * self.frame.scale)
+    }
+}
+
+pub(crate) fn layout_text(fonts: &Fonts, text: &str, font_size: f32, color: Color32, wrap_width: f32) -> Arc<Galley> {
+    layout_aligned_text(fonts, text, font_size, color, wrap_width, Align::Center)
+}
+
+/// Lays out text aligned to `halign` of the position it's painted at.
+pub(crate) fn layout_aligned_text(
+    fonts: &Fonts,
+    text: &str,
+    font_size: f32,
+    color: Color32,
+    wrap_width: f32,
+    halign: Align,
+) -> Arc<Galley> {
+    let mut job = LayoutJob::simple(text.to_owned(), FontId::proportional(font_size), color, wrap_width);
+    job.halign = halign;
+    fonts.layout_job(job)
+}
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use egui::text::Fonts;
+use egui::{vec2, Align, Color32, Galley, Vec2};
+use slide::{ListLayer, ListMarker, ListStyle};
+
+use crate::draw::color;
+use crate::layout::layout_aligned_text;
+
+/// One item of a laid out list. Positions are relative to the top left of
+/// the list.
+pub(crate) struct ListRow {
+    pub marker: Arc<Galley>,
+    pub marker_pos: Vec2,
+    pub marker_color: Color32,
+    pub text: Arc<Galley>,
+    pub text_pos: Vec2,
+    pub text_color: Color32,
+}
+
+pub(crate) struct LaidOutList {
+    pub rows: Vec<ListRow>,
+    pub size: Vec2,
+}
+
+/// Lays out `layer` `width` canvas units wide, at `scale` points per canvas
+/// unit. `text_color` is used unless the style picks a color.
+pub(crate) fn layout_list(
+    fonts: &Fonts,
+    layer: &ListLayer,
+    style: ListStyle,
+    width: f32,
+    scale: f32,
+    text_color: Color32,
+) -> LaidOutList {
+    let font_size = (style.font_size() * scale).max(1.0);
+    let indent = style.indent() * scale;
+    let width = width * scale;
+    let marker = style.marker();
+    let gutter = if marker == ListMarker::None { 0.0 } else { font_size * 1.2 };
+    let spacing = font_size * 0.3;
+    let text_color = style.color.map_or(text_color, color);
+    let marker_color = style.marker_color.map_or(text_color, color);
+
+    let mut rows = Vec::new();
+    // Items counted so far at every depth, down to the current item's.
+    let mut counts: Vec<usize> = Vec::new();
+    let mut y = 0.0;
+    for item in &layer.items {
+        counts.resize(item.depth + 1, 0);
+        counts[item.depth] += 1;
+        let x = item.depth as f32 * indent;
+        let marker_text = marker.text(item.depth, counts[item.depth]);
+        let marker_galley =
+            layout_aligned_text(fonts, &marker_text, font_size, marker_color, f32::INFINITY, Align::LEFT);
+        let wrap_width = (width - x - gutter).max(font_size);
+        let text = layout_aligned_text(fonts, item.text, font_size, text_color, wrap_width, Align::LEFT);
+        let height = marker_galley.size().y.max(text.size().y);
+        rows.push(ListRow {
+            marker: marker_galley,
+            marker_pos: vec2(x, y),
+            marker_color,
+            text,
+            text_pos: vec2(x + gutter, y),
+            text_color,
+        });
+        y += height + spacing;
+    }
+    LaidOutList {
+        rows,
+        size: vec2(width, (y - spacing).max(0.0)),
+    }
+}
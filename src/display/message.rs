@@ -0,0 +1,100 @@
+use std::time::Duration;
+
+use egui::{pos2, vec2, Align, Color32, Context, Rect, Ui};
+
+use crate::layout::{layout_aligned_text, layout_text, Frame};
+
+// Sizes are in canvas units.
+const FONT_SIZE: f32 = 48.0;
+const PADDING: f32 = 24.0;
+const BAR_COLOR: Color32 = Color32::from_black_alpha(210);
+// In canvas units per second.
+const TICKER_SPEED: f32 = 240.0;
+// How long messages fade in and out, in seconds.
+const FADE: f64 = 0.3;
+
+/// A short notice over whatever the output shows, like a call for the
+/// parents of a child in the nursery.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+    pub text: String,
+    /// Scrolls across the bottom instead of standing in a bar.
+    pub ticker: bool,
+    /// How long it shows for, in seconds. `None` shows it until it's
+    /// dismissed.
+    pub duration: Option<f64>,
+}
+
+/// A message on screen, and when it came and goes.
+pub(crate) struct Showing {
+    pub message: Message,
+    pub shown_at: f64,
+    pub until: Option<f64>,
+}
+
+impl Showing {
+    pub fn from(message: Message, now: f64) -> Self {
+        Self {
+            until: message.duration.map(|duration| now + duration),
+            message,
+            shown_at: now,
+        }
+    }
+
+    pub fn is_gone(&self, now: f64) -> bool {
+        self.until.is_some_and(|until| now >= until + FADE)
+    }
+
+    /// Whether it's still on screen and not on its way out.
+    pub fn is_up(&self, now: f64) -> bool {
+        self.until.is_none_or(|until| now < until)
+    }
+
+    fn opacity(&self, now: f64) -> f32 {
+        let fading_in = (now - self.shown_at) / FADE;
+        let fading_out = self.until.map_or(1.0, |until| (until + FADE - now) / FADE);
+        fading_in.min(fading_out).clamp(0.0, 1.0) as f32
+    }
+
+    /// Asks for the next frame the message looks different in.
+    pub fn request_repaint(&self, ctx: &Context, now: f64) {
+        if self.message.ticker || self.opacity(now) < 1.0 {
+            ctx.request_repaint();
+        } else if let Some(until) = self.until {
+            let left = Duration::try_from_secs_f64((until - now).max(0.0)).unwrap_or(Duration::MAX);
+            ctx.request_repaint_after(left);
+        }
+    }
+
+    /// Paints the message along the bottom of `frame`, over everything.
+    pub fn paint(&self, ui: &Ui, frame: Frame, now: f64) {
+        let opacity = self.opacity(now);
+        if opacity <= 0.0 || self.message.text.is_empty() {
+            return;
+        }
+        let scale = frame.scale;
+        let width = frame.rect.width();
+        let text_color = Color32::WHITE.linear_multiply(opacity);
+        let (text, font_size) = (&self.message.text, (FONT_SIZE * scale).max(1.0));
+        let galley = ui.fonts(|fonts| {
+            if self.message.ticker {
+                layout_aligned_text(fonts, text, font_size, text_color, f32::INFINITY, Align::Min)
+            } else {
+                layout_text(fonts, text, font_size, text_color, width - 2.0 * PADDING * scale)
+            }
+        });
+        let height = galley.size().y + 2.0 * PADDING * scale;
+        let bar = Rect::from_min_size(pos2(frame.rect.left(), frame.rect.bottom() - height), vec2(width, height));
+        let painter = ui.painter_at(frame.rect);
+        painter.rect_filled(bar, 0.0, BAR_COLOR.linear_multiply(opacity));
+        let y = bar.top() + PADDING * scale;
+        if self.message.ticker {
+            // Comes in on the right and leaves on the left, then again.
+            let distance = width + galley.size().x;
+            let travelled = ((now - self.shown_at) as f32 * TICKER_SPEED * scale) % distance;
+            painter.galley(pos2(bar.right() - travelled, y), galley);
+        } else {
+            painter.galley(pos2(bar.center().x, y), galley);
+        }
+    }
+}
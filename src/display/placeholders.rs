@@ -0,0 +1,62 @@
+use std::borrow::Cow;
+
+use chrono::{DateTime, Local};
+use sil::{split_placeholders, Segment};
+use slide::Layer;
+
+/// What `{placeholders}` in text resolve to on a slide.
+pub(crate) struct Placeholders<'a> {
+    pub song_title: &'a str,
+    pub song_author: &'a str,
+    pub service_name: &'a str,
+    pub slide_index: usize,
+    pub slide_count: usize,
+    pub local: DateTime<Local>,
+}
+
+impl Placeholders<'_> {
+    /// `text` with its placeholders filled in. Unknown ones are left as
+    /// they are.
+    pub fn resolve<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        if !text.contains(['{', '}']) {
+            return Cow::Borrowed(text);
+        }
+        let mut res = String::new();
+        for segment in split_placeholders(text) {
+            match segment {
+                Segment::Text(text) => res.push_str(text),
+                Segment::Placeholder(name) => match self.value(name) {
+                    Some(value) => res.push_str(&value),
+                    None => res.push_str(&format!("{{{name}}}")),
+                },
+            }
+        }
+        Cow::Owned(res)
+    }
+
+    fn value(&self, name: &str) -> Option<String> {
+        match name {
+            "date" => Some(self.local.format("%B %-d, %Y").to_string()),
+            "time" => Some(self.local.format("%H:%M").to_string()),
+            "song.title" => Some(self.song_title.to_owned()),
+            "song.author" => Some(self.song_author.to_owned()),
+            "slide.index" => Some((self.slide_index + 1).to_string()),
+            "slide.count" => Some(self.slide_count.to_string()),
+            "service.name" => Some(self.service_name.to_owned()),
+            _ => None,
+        }
+    }
+}
+
+/// Whether `layer` shows the date or time, so it needs redrawing as the
+/// clock moves on.
+pub(crate) fn shows_time(layer: &Layer) -> bool {
+    let text = match layer {
+        Layer::Text(layer) => layer.text,
+        Layer::Title(layer) => layer.text,
+        _ => return false,
+    };
+    split_placeholders(text)
+        .iter()
+        .any(|segment| matches!(segment, Segment::Placeholder("date" | "time")))
+}
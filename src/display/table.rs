@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use egui::text::Fonts;
+use egui::{pos2, vec2, Align, Color32, Galley, Rect, Vec2};
+use slide::{TableLayer, TableStyle};
+
+use crate::draw::color;
+use crate::layout::layout_aligned_text;
+
+pub(crate) struct TableCell {
+    pub galley: Arc<Galley>,
+    /// Where the galley is painted, relative to the top left of the table.
+    pub pos: Vec2,
+}
+
+pub(crate) struct TableRow {
+    pub cells: Vec<TableCell>,
+    /// Relative to the top left of the table.
+    pub rect: Rect,
+    pub color: Color32,
+    pub is_header: bool,
+}
+
+pub(crate) struct LaidOutTable {
+    pub rows: Vec<TableRow>,
+    pub size: Vec2,
+}
+
+/// Lays out `layer` `width` canvas units wide, at `scale` points per canvas
+/// unit. Columns get their natural width, and share what's left over or
+/// shrink in proportion when there's too little room.
+pub(crate) fn layout_table(
+    fonts: &Fonts,
+    layer: &TableLayer,
+    style: TableStyle,
+    width: f32,
+    scale: f32,
+    text_color: Color32,
+) -> LaidOutTable {
+    let font_size = (style.font_size() * scale).max(1.0);
+    let padding = style.padding() * scale;
+    let width = width * scale;
+    let columns = layer.column_count();
+    let text_color = style.color.map_or(text_color, color);
+    let header_color = style.header_color.map_or(text_color, color);
+
+    let mut natural = vec![0.0_f32; columns];
+    for row in &layer.rows {
+        for (column, cell) in row.iter().enumerate() {
+            let size = layout_aligned_text(fonts, cell, font_size, text_color, f32::INFINITY, Align::LEFT).size();
+            natural[column] = natural[column].max(size.x);
+        }
+    }
+    let total: f32 = natural.iter().sum();
+    let available = (width - columns as f32 * 2.0 * padding).max(0.0);
+    let column_widths: Vec<f32> = natural
+        .iter()
+        .map(|&natural| {
+            if total <= available {
+                natural + (available - total) / columns as f32
+            } else {
+                available * natural / total
+            }
+        })
+        .collect();
+
+    let mut rows = Vec::new();
+    let mut y = 0.0;
+    for (index, row) in layer.rows.iter().enumerate() {
+        let is_header = layer.header && index == 0;
+        let color = if is_header { header_color } else { text_color };
+        let mut x = 0.0;
+        let mut cells = Vec::new();
+        let mut height: f32 = 0.0;
+        for (column, cell) in row.iter().enumerate() {
+            let cell_width = column_widths[column];
+            let (halign, offset) = match layer.align(column) {
+                slide::Align::Left => (Align::LEFT, 0.0),
+                slide::Align::Center => (Align::Center, cell_width / 2.0),
+                slide::Align::Right => (Align::RIGHT, cell_width),
+            };
+            let galley = layout_aligned_text(fonts, cell, font_size, color, cell_width.max(1.0), halign);
+            height = height.max(galley.size().y);
+            cells.push(TableCell {
+                galley,
+                pos: vec2(x + padding + offset, y + padding),
+            });
+            x += cell_width + 2.0 * padding;
+        }
+        let row_height = height + 2.0 * padding;
+        rows.push(TableRow {
+            cells,
+            rect: Rect::from_min_size(pos2(0.0, y), vec2(width, row_height)),
+            color,
+            is_header,
+        });
+        y += row_height;
+    }
+    LaidOutTable {
+        rows,
+        size: vec2(width, y),
+    }
+}
@@ -0,0 +1,41 @@
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local, Timelike};
+use slide::{AtZero, Slide, TimerKind, TimerLayer};
+
+/// The time timers show.
+#[derive(Clone, Copy)]
+pub(crate) struct Clock {
+    /// Seconds since the slide was shown.
+    pub elapsed: f64,
+    pub local: DateTime<Local>,
+}
+
+impl Clock {
+    /// Seconds after midnight, local time.
+    pub fn time_of_day(&self) -> f64 {
+        self.local.num_seconds_from_midnight() as f64 + self.local.nanosecond() as f64 / 1e9
+    }
+
+    /// What `layer` shows right now, if anything.
+    pub fn text(&self, layer: &TimerLayer) -> Option<String> {
+        if layer.kind != TimerKind::Clock {
+            return layer.text(self.elapsed, self.time_of_day());
+        }
+        let items: Vec<Item> = StrftimeItems::new(layer.format).collect();
+        if items.contains(&Item::Error) {
+            return Some(format!("Invalid clock format: {}", layer.format));
+        }
+        Some(self.local.format_with_items(items.into_iter()).to_string())
+    }
+
+    /// Whether a countdown on `slide` set to advance ran out in the last
+    /// `step` seconds.
+    pub fn ran_out(&self, slide: &Slide, step: f64) -> bool {
+        let before = self.elapsed - step;
+        slide.timers().any(|timer| {
+            timer.at_zero == AtZero::Advance
+                && timer.remaining(self.elapsed, self.time_of_day()) == Some(0.0)
+                && timer.remaining(before, self.time_of_day() - step).is_some_and(|remaining| remaining > 0.0)
+        })
+    }
+}
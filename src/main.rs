@@ -1,33 +1,59 @@
 use bevy::core_pipeline::core_3d::Camera3dBundle;
+use bevy::ecs::prelude::Res;
 use bevy::ecs::prelude::ResMut;
+use bevy::ecs::component::Component;
 use bevy::ecs::query::With;
-use bevy::ecs::query::Without;
+use bevy::ecs::entity::Entity;
 use bevy::ecs::system::Commands;
+use bevy::ecs::system::Local;
+use bevy::ecs::system::NonSend;
 use bevy::ecs::system::Query;
 use bevy::math::Vec3;
 use bevy::render::camera::Camera;
 use bevy::render::camera::RenderTarget;
+use bevy::time::Time;
 use bevy::transform::components::Transform;
 use bevy::window::PresentMode;
 use bevy::window::PrimaryWindow;
 use bevy::window::Window;
 use bevy::window::WindowRef;
+use bevy::window::MonitorSelection;
+use bevy::window::WindowMode;
+use bevy::window::WindowPosition;
 use bevy::window::WindowResolution;
+use bevy::hierarchy::DespawnRecursiveExt;
+use bevy::window::WindowCloseRequested;
+use bevy::window::WindowPlugin;
+use bevy::winit::WinitWindows;
+use bevy::app::AppExit;
+use bevy::app::PluginGroup;
+use bevy::ecs::event::EventReader;
+use bevy::ecs::event::EventWriter;
 use bevy::DefaultPlugins;
 use bevy_egui::{EguiContext, EguiPlugin};
+use project::WindowSettings;
 use root_path::RootPath;
 
 fn main() -> Result<(), &'static str> {
     bevy::prelude::App::new()
-        .add_plugins(DefaultPlugins)
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            // Closing the control window asks first, see `close_requested`.
+            close_when_requested: false,
+            ..Default::default()
+        }))
         .add_plugin(EguiPlugin)
         .insert_resource(app::App::from(
             fetch_root_folder().ok_or("invalid root path")?,
         ))
-        // .add_startup_system(create_display_window)
+        .add_system(advance_time)
+        .add_system(count_monitors)
+        .add_system(sync_output_window)
+        .add_system(sync_stage_window)
         .add_system(control_window)
         .add_system(display_window)
-        .add_system(bevy::window::close_on_esc)
+        .add_system(stage_window)
+        .add_system(close_requested)
+        .add_system(exit_when_confirmed)
         .run();
 
     Ok(())
@@ -43,24 +69,175 @@ fn fetch_root_folder() -> Option<RootPath> {
     return Some(RootPath::from(path));
 }
 
-fn create_display_window(mut commands: Commands) {
-    let display_window_id = commands
-        .spawn(Window {
-            title: "output".to_owned(),
-            resolution: WindowResolution::new(800.0, 600.0),
-            present_mode: PresentMode::AutoVsync,
+/// Marks the window the audience sees.
+#[derive(Component, Clone, Copy)]
+struct OutputWindow;
+
+/// Marks the window facing the band.
+#[derive(Component, Clone, Copy)]
+struct StageWindow;
+
+/// A window, once spawned, and the camera drawing into it.
+#[derive(Default)]
+struct SpawnedWindow {
+    window: Option<Entity>,
+    camera: Option<Entity>,
+    applied: Option<WindowSettings>,
+}
+
+/// Tells the app how many monitors there are, as seen from the control
+/// window.
+fn count_monitors(
+    mut app: ResMut<app::App>,
+    winit_windows: NonSend<WinitWindows>,
+    primary: Query<Entity, With<PrimaryWindow>>,
+) {
+    let Some(window) = primary.get_single().ok().and_then(|entity| winit_windows.get_window(entity)) else {
+        return;
+    };
+    app.set_monitors(window.available_monitors().count());
+}
+
+/// Opens, closes and updates the output window to match the control
+/// window's settings.
+fn sync_output_window(
+    mut commands: Commands,
+    mut app: ResMut<app::App>,
+    mut windows: Query<&mut Window, With<OutputWindow>>,
+    mut spawned: Local<SpawnedWindow>,
+) {
+    let wanted = app.output_window();
+    if sync_window(&mut commands, &mut windows, &mut spawned, wanted, OutputWindow) {
+        app.output_window_closed();
+    }
+}
+
+fn sync_stage_window(
+    mut commands: Commands,
+    mut app: ResMut<app::App>,
+    mut windows: Query<&mut Window, With<StageWindow>>,
+    mut spawned: Local<SpawnedWindow>,
+) {
+    let wanted = app.stage_window();
+    if sync_window(&mut commands, &mut windows, &mut spawned, wanted, StageWindow) {
+        app.stage_window_closed();
+    }
+}
+
+/// Spawns, despawns or updates a window marked with `marker` to match
+/// `wanted`. Moving it to another monitor spawns it again there, and a
+/// fullscreen window goes fullscreen once it's open. Returns whether the
+/// window was closed some other way, like by the window manager.
+fn sync_window<T: Component + Copy>(
+    commands: &mut Commands,
+    windows: &mut Query<&mut Window, With<T>>,
+    spawned: &mut SpawnedWindow,
+    wanted: Option<WindowSettings>,
+    marker: T,
+) -> bool {
+    let closed = spawned.window.is_some_and(|window| windows.get(window).is_err());
+    if closed {
+        despawn_window(commands, spawned);
+        return true;
+    }
+
+    if wanted == spawned.applied {
+        return false;
+    }
+    let same_monitor = spawned.applied.as_ref().map(|applied| applied.monitor) == wanted.as_ref().map(|w| w.monitor);
+    match (spawned.window, wanted) {
+        (Some(window), Some(wanted)) if same_monitor => {
+            if let Ok(mut window) = windows.get_mut(window) {
+                configure_window(&mut window, &wanted);
+            }
+            spawned.applied = Some(wanted);
+        }
+        (_, wanted) => {
+            despawn_window(commands, spawned);
+            if let Some(wanted) = wanted {
+                spawn_window(commands, spawned, wanted, marker);
+            }
+        }
+    }
+    false
+}
+
+fn spawn_window(
+    commands: &mut Commands,
+    spawned: &mut SpawnedWindow,
+    config: WindowSettings,
+    marker: impl Component,
+) {
+    // Fullscreen windows open on the primary monitor whatever their
+    // position. So the window opens windowed on its monitor, and
+    // `sync_window` makes it fullscreen there on the next frame.
+    let config = WindowSettings {
+        fullscreen: false,
+        ..config
+    };
+    let mut window = Window::default();
+    configure_window(&mut window, &config);
+    let window = commands.spawn((window, marker)).id();
+
+    let camera = commands
+        .spawn(Camera3dBundle {
+            camera: Camera {
+                target: RenderTarget::Window(WindowRef::Entity(window)),
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(6.0, 0.0, 0.0).looking_at(Vec3::ZERO, Vec3::Y),
             ..Default::default()
         })
         .id();
 
-    commands.spawn(Camera3dBundle {
-        camera: Camera {
-            target: RenderTarget::Window(WindowRef::Entity(display_window_id)),
-            ..Default::default()
-        },
-        transform: Transform::from_xyz(6.0, 0.0, 0.0).looking_at(Vec3::ZERO, Vec3::Y),
-        ..Default::default()
-    });
+    spawned.window = Some(window);
+    spawned.camera = Some(camera);
+    spawned.applied = Some(config);
+}
+
+fn despawn_window(commands: &mut Commands, spawned: &mut SpawnedWindow) {
+    for entity in [spawned.camera.take(), spawned.window.take()].into_iter().flatten() {
+        if let Some(entity) = commands.get_entity(entity) {
+            entity.despawn_recursive();
+        }
+    }
+    spawned.applied = None;
+}
+
+fn configure_window(window: &mut Window, config: &WindowSettings) {
+    window.title = config.title.clone();
+    window.resolution = WindowResolution::new(config.width, config.height);
+    window.present_mode = if config.vsync { PresentMode::AutoVsync } else { PresentMode::AutoNoVsync };
+    window.position = WindowPosition::Centered(MonitorSelection::Index(config.monitor));
+    window.mode = if config.fullscreen { WindowMode::BorderlessFullscreen } else { WindowMode::Windowed };
+    window.decorations = !config.fullscreen;
+}
+
+/// Closes the output and stage windows right away, but asks before closing
+/// the control window, which quits.
+fn close_requested(
+    mut commands: Commands,
+    mut app: ResMut<app::App>,
+    mut requests: EventReader<WindowCloseRequested>,
+    primary: Query<(), With<PrimaryWindow>>,
+) {
+    for request in requests.iter() {
+        if primary.contains(request.window) {
+            app.request_quit();
+        } else if let Some(window) = commands.get_entity(request.window) {
+            window.despawn_recursive();
+        }
+    }
+}
+
+fn exit_when_confirmed(app: Res<app::App>, mut exit: EventWriter<AppExit>) {
+    if app.quit_confirmed() {
+        exit.send(AppExit);
+    }
+}
+
+fn advance_time(mut app: ResMut<app::App>, time: Res<Time>) {
+    app.as_mut().set_time(time.elapsed_seconds_f64());
 }
 
 fn control_window(
@@ -73,8 +250,16 @@ fn control_window(
 
 fn display_window(
     mut app: ResMut<app::App>,
-    mut egui_ctx: Query<&mut EguiContext, Without<PrimaryWindow>>,
+    mut egui_ctx: Query<&mut EguiContext, With<OutputWindow>>,
 ) {
     let Ok(mut ctx) = egui_ctx.get_single_mut() else { return; };
     app.as_mut().draw_display_window(ctx.get_mut());
 }
+
+fn stage_window(
+    mut app: ResMut<app::App>,
+    mut egui_ctx: Query<&mut EguiContext, With<StageWindow>>,
+) {
+    let Ok(mut ctx) = egui_ctx.get_single_mut() else { return; };
+    app.as_mut().draw_stage_window(ctx.get_mut());
+}
@@ -0,0 +1,130 @@
+use sil::{Node, SectionKind};
+
+/// What a key can do in the control, output and stage windows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Moves the output to the next slide or part of a build.
+    NextSlide,
+    PreviousSlide,
+    /// Moves to the next item of the service. Not bound by default, as the
+    /// service list only holds stand-ins so far.
+    NextItem,
+    PreviousItem,
+    /// Sends the preview to the output.
+    GoLive,
+    /// Steps the output back to the slide live before.
+    Undo,
+    /// Plays on after moving by hand paused a presentation.
+    Resume,
+    ClearText,
+    ClearBackground,
+    Black,
+    Logo,
+    /// Lifts whatever clear is on, showing everything again.
+    ShowAll,
+    /// Jumps the output to the next section of a kind. Holding the key
+    /// and pressing a digit jumps to that verse or chorus instead.
+    Section(SectionKind),
+    FocusEditor,
+    /// Opens or closes the output window.
+    OutputWindow,
+    /// Opens or closes the stage window.
+    StageWindow,
+    /// Asks whether to quit.
+    Quit,
+}
+
+impl Action {
+    pub fn from(s: &str) -> Option<Self> {
+        match s {
+            "next" => Some(Action::NextSlide),
+            "previous" => Some(Action::PreviousSlide),
+            "next_item" => Some(Action::NextItem),
+            "previous_item" => Some(Action::PreviousItem),
+            "go_live" => Some(Action::GoLive),
+            "undo" => Some(Action::Undo),
+            "resume" => Some(Action::Resume),
+            "clear_text" => Some(Action::ClearText),
+            "clear_background" => Some(Action::ClearBackground),
+            "black" => Some(Action::Black),
+            "logo" => Some(Action::Logo),
+            "show_all" => Some(Action::ShowAll),
+            "verse" => Some(Action::Section(SectionKind::Verse)),
+            "chorus" => Some(Action::Section(SectionKind::Chorus)),
+            "pre_chorus" => Some(Action::Section(SectionKind::PreChorus)),
+            "bridge" => Some(Action::Section(SectionKind::Bridge)),
+            "ending" => Some(Action::Section(SectionKind::Ending)),
+            "focus_editor" => Some(Action::FocusEditor),
+            "output_window" => Some(Action::OutputWindow),
+            "stage_window" => Some(Action::StageWindow),
+            "quit" => Some(Action::Quit),
+            _ => None,
+        }
+    }
+}
+
+/// Keys bound to actions, from the `[keys]` block. Each attribute names an
+/// action and lists its keys, like `next = "Right PageDown"`, replacing
+/// the default ones. Keys take `Ctrl+`, `Shift+` and `Alt+` in front.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keymap {
+    /// Keys as written, and what they do.
+    pub bindings: Vec<(String, Action)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = [
+            // Presenter clickers send page up and down.
+            ("Right", Action::NextSlide),
+            ("PageDown", Action::NextSlide),
+            ("Space", Action::NextSlide),
+            ("Left", Action::PreviousSlide),
+            ("PageUp", Action::PreviousSlide),
+            ("Ctrl+Enter", Action::GoLive),
+            ("Backspace", Action::Undo),
+            ("F5", Action::Resume),
+            ("F1", Action::ClearText),
+            ("F2", Action::ClearBackground),
+            ("F3", Action::Black),
+            ("F4", Action::Logo),
+            ("F6", Action::ShowAll),
+            ("V", Action::Section(SectionKind::Verse)),
+            ("C", Action::Section(SectionKind::Chorus)),
+            ("P", Action::Section(SectionKind::PreChorus)),
+            ("B", Action::Section(SectionKind::Bridge)),
+            ("E", Action::Section(SectionKind::Ending)),
+            ("Ctrl+E", Action::FocusEditor),
+            ("F11", Action::OutputWindow),
+            ("F12", Action::StageWindow),
+            ("Ctrl+Q", Action::Quit),
+        ];
+        Self {
+            bindings: bindings.into_iter().map(|(keys, action)| (keys.to_owned(), action)).collect(),
+        }
+    }
+}
+
+impl From<&Node> for Keymap {
+    fn from(value: &Node) -> Self {
+        let mut res = Self::default();
+        for attribute in value.attributes() {
+            let Some(action) = Action::from(attribute.name()) else {
+                // FIXME: Add garbage attribute
+                continue;
+            };
+            res.bindings.retain(|(_, bound)| *bound != action);
+            for keys in attribute.value_text().split_whitespace() {
+                res.bindings.push((keys.to_owned(), action));
+            }
+        }
+        res
+    }
+}
+
+impl Keymap {
+    /// The keys bound to `action`, as written.
+    pub fn keys(&self, action: Action) -> impl Iterator<Item = &str> {
+        self.bindings.iter().filter(move |(_, bound)| *bound == action).map(|(keys, _)| keys.as_str())
+    }
+}
@@ -0,0 +1,368 @@
+mod keymap;
+
+use sil::AstNode;
+use sil::Attribute;
+use sil::Node;
+use slide::{parse_bool, Transition};
+
+pub use crate::keymap::*;
+
+/// Size of the virtual canvas slides are laid out on, in canvas units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Canvas {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Default for Canvas {
+    fn default() -> Self {
+        Self {
+            width: 1920.0,
+            height: 1080.0,
+        }
+    }
+}
+
+impl From<&Node> for Canvas {
+    fn from(value: &Node) -> Self {
+        let mut res = Self::default();
+        for attribute in value.attributes() {
+            let text = attribute.value_text();
+            match attribute.name() {
+                "width" => res.width = text.parse::<f32>().unwrap_or(res.width).max(1.0),
+                "height" => res.height = text.parse::<f32>().unwrap_or(res.height).max(1.0),
+                _ => {}
+            }
+        }
+        res
+    }
+}
+
+/// The service a project is put together for.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Service {
+    pub name: String,
+}
+
+impl From<&Node> for Service {
+    fn from(value: &Node) -> Self {
+        let mut res = Self::default();
+        for attribute in value.attributes() {
+            if attribute.name() == "name" {
+                res.name = attribute.value_text().to_owned();
+            }
+        }
+        res
+    }
+}
+
+/// A window spawned on a monitor of its own, like the output.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowSettings {
+    pub title: String,
+    /// Size when not fullscreen, in logical pixels.
+    pub width: f32,
+    pub height: f32,
+    pub vsync: bool,
+    /// Index of the monitor it opens on.
+    pub monitor: usize,
+    /// Borderless fullscreen instead of a plain window.
+    pub fullscreen: bool,
+}
+
+impl Default for WindowSettings {
+    fn default() -> Self {
+        Self {
+            title: "output".to_owned(),
+            width: 800.0,
+            height: 600.0,
+            vsync: true,
+            monitor: 1,
+            fullscreen: true,
+        }
+    }
+}
+
+impl From<&Node> for WindowSettings {
+    fn from(value: &Node) -> Self {
+        let mut res = Self::default();
+        for attribute in value.attributes() {
+            res.parse(attribute);
+        }
+        res
+    }
+}
+
+impl WindowSettings {
+    pub fn parse(&mut self, attribute: &Attribute) {
+        let text = attribute.value_text();
+        match attribute.name() {
+            "title" => self.title = text.to_owned(),
+            "width" => self.width = text.parse::<f32>().unwrap_or(self.width).max(1.0),
+            "height" => self.height = text.parse::<f32>().unwrap_or(self.height).max(1.0),
+            "vsync" => self.vsync = parse_bool(text).unwrap_or(self.vsync),
+            "monitor" => self.monitor = text.parse().unwrap_or(self.monitor),
+            "fullscreen" => self.fullscreen = parse_bool(text).unwrap_or(self.fullscreen),
+            _ => {}
+        }
+    }
+
+    /// The settings fit to a machine with `monitors` monitors. Missing
+    /// monitors fall back to the last one, and with only one the window
+    /// stays windowed instead of covering the control window.
+    pub fn on_monitors(&self, monitors: usize) -> Self {
+        Self {
+            monitor: self.monitor.min(monitors.saturating_sub(1)),
+            fullscreen: self.fullscreen && monitors > 1,
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum StageLayout {
+    /// The live slide on the left, everything else in a column beside it.
+    #[default]
+    Side,
+    /// The live slide on top, everything else in a row below it.
+    Stacked,
+}
+
+impl StageLayout {
+    pub fn from(s: &str) -> Option<Self> {
+        match s {
+            "side" => Some(StageLayout::Side),
+            "stacked" => Some(StageLayout::Stacked),
+            _ => None,
+        }
+    }
+}
+
+/// The display facing the band, set up by a `[stage]` block.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stage {
+    pub window: WindowSettings,
+    pub layout: StageLayout,
+    /// Size of the clock, timers and notes, in points.
+    pub font_size: f32,
+}
+
+impl Default for Stage {
+    fn default() -> Self {
+        Self {
+            window: WindowSettings {
+                title: "stage".to_owned(),
+                monitor: 2,
+                ..WindowSettings::default()
+            },
+            layout: StageLayout::default(),
+            font_size: 48.0,
+        }
+    }
+}
+
+impl From<&Node> for Stage {
+    fn from(value: &Node) -> Self {
+        let mut res = Self::default();
+        for attribute in value.attributes() {
+            let text = attribute.value_text();
+            match attribute.name() {
+                "layout" => res.layout = StageLayout::from(text).unwrap_or(res.layout),
+                "font_size" => res.font_size = text.parse::<f32>().unwrap_or(res.font_size).max(1.0),
+                _ => res.window.parse(attribute),
+            }
+        }
+        res
+    }
+}
+
+/// The longest a message shows for before it has to be dismissed, in
+/// seconds.
+pub const MAX_MESSAGE_DURATION: f64 = 600.0;
+
+/// A message the operator can start from, set up by a `[message]` block
+/// with the text as its body.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MessageTemplate {
+    pub text: String,
+    pub ticker: bool,
+    /// In seconds. `None` shows it until it's dismissed.
+    pub duration: Option<f64>,
+}
+
+impl From<&Node> for MessageTemplate {
+    fn from(value: &Node) -> Self {
+        let mut res = Self {
+            text: value.text().trim().to_owned(),
+            ..Self::default()
+        };
+        for attribute in value.attributes() {
+            let text = attribute.value_text();
+            match attribute.name() {
+                "ticker" => res.ticker = parse_bool(text).unwrap_or(res.ticker),
+                "duration" => {
+                    let duration = text.parse::<f64>().ok().filter(|seconds| seconds.is_finite() && *seconds > 0.0);
+                    res.duration = duration.map(|duration| duration.min(MAX_MESSAGE_DURATION));
+                }
+                _ => {}
+            }
+        }
+        res
+    }
+}
+
+/// Per project settings, loaded from `project.sil` in the project root.
+#[derive(Debug, PartialEq)]
+pub struct Project {
+    pub canvas: Canvas,
+    /// Used by slides and themes that don't pick their own.
+    pub transition: Transition,
+    pub service: Service,
+    pub output_window: WindowSettings,
+    /// Only set when the project has a `[stage]` block.
+    pub stage: Option<Stage>,
+    /// How long clearing the output fades for, in seconds.
+    pub clear_fade: f32,
+    pub keymap: Keymap,
+    pub message_templates: Vec<MessageTemplate>,
+}
+
+impl Default for Project {
+    fn default() -> Self {
+        Self {
+            canvas: Canvas::default(),
+            transition: Transition::default(),
+            service: Service::default(),
+            output_window: WindowSettings::default(),
+            stage: None,
+            clear_fade: 0.5,
+            keymap: Keymap::default(),
+            message_templates: Vec::new(),
+        }
+    }
+}
+
+impl Project {
+    pub fn from(content: &str) -> Self {
+        // Nothing parsed here outlives `content`.
+        let tokens = sil::lex(unsafe { &*(content as *const str) });
+        let ast = sil::parse(&tokens);
+
+        let mut res = Self::default();
+        for block in ast.view() {
+            if let &AstNode::Node(id) = block {
+                let node = &ast[id];
+                match node.kind {
+                    "canvas" => res.canvas = Canvas::from(node),
+                    "transition" => res.transition = Transition::from(node),
+                    "service" => res.service = Service::from(node),
+                    "output" => res.output_window = WindowSettings::from(node),
+                    "stage" => res.stage = Some(Stage::from(node)),
+                    "clear" => res.clear_fade = clear_fade(node).unwrap_or(res.clear_fade),
+                    "keys" => res.keymap = Keymap::from(node),
+                    "message" => res.message_templates.push(MessageTemplate::from(node)),
+                    _ => {}
+                }
+            }
+        }
+        res
+    }
+
+    pub fn load(root: &str) -> Self {
+        let path = std::path::Path::new(root).join("project.sil");
+        Self::from(std::fs::read_to_string(path).unwrap_or_default().as_str())
+    }
+}
+
+/// The `fade` of a `[clear]` block, in seconds.
+fn clear_fade(node: &Node) -> Option<f32> {
+    let fade = node.attributes().iter().find(|attribute| attribute.name() == "fade")?;
+    fade.value_text().parse::<f32>().ok().map(|fade| fade.max(0.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn can_parse_canvas() {
+        let project = Project::from("[canvas] width = 1280 height = 720");
+        assert_eq!(
+            project.canvas,
+            Canvas {
+                width: 1280.0,
+                height: 720.0
+            }
+        );
+    }
+
+    #[test]
+    fn can_parse_service() {
+        let project = Project::from("[service] name = \"Sunday Morning\"");
+        assert_eq!(project.service.name, "Sunday Morning");
+    }
+
+    #[test]
+    fn can_parse_output_window() {
+        let project = Project::from("[output] monitor = 2 fullscreen = \"no\" vsync = \"false\"");
+        assert_eq!(project.output_window.monitor, 2);
+        assert!(!project.output_window.fullscreen);
+        assert!(!project.output_window.vsync);
+        assert_eq!(project.output_window.title, "output");
+    }
+
+    #[test]
+    fn can_parse_stage() {
+        let project = Project::from("[stage] layout = \"stacked\" monitor = 0");
+        let stage = project.stage.unwrap();
+        assert_eq!(stage.layout, StageLayout::Stacked);
+        assert_eq!(stage.window.monitor, 0);
+        assert_eq!(stage.window.title, "stage");
+    }
+
+    #[test]
+    fn fits_windows_to_monitors() {
+        let window = WindowSettings::default();
+        assert_eq!(window.on_monitors(3), window);
+        let single = window.on_monitors(1);
+        assert_eq!((single.monitor, single.fullscreen), (0, false));
+        let pair = Stage::default().window.on_monitors(2);
+        assert_eq!((pair.monitor, pair.fullscreen), (1, true));
+    }
+
+    #[test]
+    fn can_parse_keymap() {
+        let project = Project::from("[keys] next = \"PageDown B\" quit = \"Ctrl+Shift+Q\"");
+        let keymap = project.keymap;
+        assert_eq!(keymap.keys(Action::NextSlide).collect::<Vec<_>>(), ["PageDown", "B"]);
+        assert_eq!(keymap.keys(Action::Quit).collect::<Vec<_>>(), ["Ctrl+Shift+Q"]);
+        assert_eq!(keymap.keys(Action::GoLive).collect::<Vec<_>>(), ["Ctrl+Enter"]);
+        assert_eq!(keymap.keys(Action::NextItem).count(), 0);
+    }
+
+    #[test]
+    fn can_parse_message_templates() {
+        let project =
+            Project::from("[message] ticker = \"yes\" duration = 30\nParent of child # please come to the nursery");
+        assert_eq!(
+            project.message_templates,
+            [MessageTemplate {
+                text: "Parent of child # please come to the nursery".to_owned(),
+                ticker: true,
+                duration: Some(30.0),
+            }]
+        );
+        let project = Project::from("[message] duration = \"inf\"\nA\n[message] duration = \"1e20\"\nB");
+        let durations: Vec<Option<f64>> = project
+            .message_templates
+            .iter()
+            .map(|template| template.duration)
+            .collect();
+        assert_eq!(durations, [None, Some(MAX_MESSAGE_DURATION)]);
+    }
+
+    #[test]
+    fn defaults_to_full_hd() {
+        assert_eq!(Project::from("").canvas, Canvas::default());
+    }
+}
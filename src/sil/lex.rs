@@ -44,7 +44,7 @@ impl Token {
     pub fn text(&self) -> &'static str {
         match self {
             Token::Quoted(text) => remove_quotes(text),
-            _ => self.text(),
+            _ => self.raw_text(),
         }
     }
 }
@@ -59,10 +59,13 @@ fn remove_quotes(quoted: &'static str) -> &'static str {
     return &quoted[1..];
 }
 
-pub fn lex(mut content: &'static str) -> Vec<Token> {
+pub fn lex(source: &'static str) -> Vec<Token> {
     let mut tokens: Vec<Token> = Vec::new();
 
+    let mut content = source;
     while !content.is_empty() {
+        let offset = source.len() - content.len();
+        let line_start = offset == 0 || source[..offset].ends_with('\n');
         match content.as_bytes() {
             [b'0'..=b'9', ..] => {
                 let res = lex_number(content);
@@ -70,6 +73,14 @@ pub fn lex(mut content: &'static str) -> Vec<Token> {
                 content = &content[res.len()..];
             }
 
+            // Indentation in front of text starting a line is kept with the
+            // text, so bodies know how far their first line is indented.
+            [b' ', ..] if line_start && starts_text(content.trim_start_matches(' ')) => {
+                let res = lex_text(content);
+                tokens.push(Token::Text(remove_trailing_new_lines(res)));
+                content = &content[res.len()..];
+            }
+
             [b' ' | b'\n', ..] => {
                 content = &content[1..];
             }
@@ -113,6 +124,12 @@ pub fn lex(mut content: &'static str) -> Vec<Token> {
     return tokens;
 }
 
+/// Whether `content` starts with what `lex` reads as text.
+fn starts_text(content: &str) -> bool {
+    let not_text = |byte: &u8| matches!(byte, b'0'..=b'9' | b' ' | b'\n' | b'"' | b'[' | b']' | b':' | b'=');
+    content.as_bytes().first().is_some_and(|byte| !not_text(byte))
+}
+
 fn lex_text(content: &str) -> &str {
     let mut end_index = 0;
 
@@ -173,6 +190,8 @@ fn lex_quoted(content: &str) -> &str {
 }
 
 pub fn clean_up_for_attribute_key(content: &str) -> &str {
+    // Attributes on a line of their own are often indented.
+    let content = content.trim_start_matches([' ', '\t']);
     let mut trailing_spaces = 0;
     let chars = content.chars();
     for c in chars.rev() {
@@ -233,6 +252,28 @@ mod tests {
         assert_eq!(lex("\"black\""), [Token::Quoted("\"black\"")]);
     }
 
+    #[test]
+    fn text_removes_quotes() {
+        assert_eq!(Token::Quoted("\"media/cross.png\"").text(), "media/cross.png");
+        assert_eq!(Token::Number("42").text(), "42");
+    }
+
+    #[test]
+    fn text_of_unquoted_tokens_is_raw() {
+        // `text` used to call itself for anything but quoted tokens.
+        for token in [Token::Number("1"), Token::Text("a"), Token::open_bracket(), Token::equal_sign()] {
+            assert_eq!(token.text(), token.raw_text());
+        }
+    }
+
+    #[test]
+    fn keeps_indentation_of_lines_with_text() {
+        let tokens = lex("[list]\n  One\n    Two\n  width = 3");
+        assert_eq!(tokens[3], Token::Text("  One\n    Two\n  width "));
+        assert_eq!(clean_up_for_attribute_key("  width "), "width");
+        assert_eq!(lex("[text] 42\n  7"), lex("[text] 42\n7"));
+    }
+
     #[test]
     fn can_lex_incomplete_quoted() {
         assert_eq!(lex("\"black"), [Token::Quoted("\"black")]);
@@ -0,0 +1,86 @@
+use crate::AstNode;
+use crate::Node;
+use crate::AST;
+
+/// What a `[meta]` block says about a song.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Meta {
+    pub title: Option<&'static str>,
+    pub authors: Vec<&'static str>,
+    pub ccli: Option<&'static str>,
+    pub copyright: Option<&'static str>,
+    pub key: Option<&'static str>,
+    /// Beats per minute.
+    pub tempo: Option<f32>,
+    pub themes: Vec<&'static str>,
+    pub tags: Vec<&'static str>,
+}
+
+impl Meta {
+    /// The metadata of the last `[meta]` block in `ast`.
+    pub fn from_ast(ast: &AST) -> Self {
+        ast.view()
+            .iter()
+            .rev()
+            .find_map(|block| match block {
+                &AstNode::Node(id) if ast[id].kind == "meta" => Some(Meta::from(&ast[id])),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    /// The authors, joined for display.
+    pub fn author(&self) -> String {
+        self.authors.join(", ")
+    }
+}
+
+impl From<&Node> for Meta {
+    fn from(value: &Node) -> Self {
+        let mut res = Self::default();
+        for attribute in value.attributes() {
+            let text = attribute.value_text();
+            match attribute.name() {
+                "title" => res.title = Some(text),
+                "authors" | "author" => res.authors = split_list(text),
+                "ccli" => res.ccli = Some(text),
+                "copyright" => res.copyright = Some(text),
+                "key" => res.key = Some(text),
+                "tempo" => res.tempo = text.parse().ok(),
+                "themes" => res.themes = split_list(text),
+                "tags" => res.tags = split_list(text),
+                _ => {
+                    // FIXME: Add garbage attribute
+                }
+            }
+        }
+        res
+    }
+}
+
+/// Splits comma separated values, like `"John Newton, Edwin Excell"`.
+fn split_list(text: &'static str) -> Vec<&'static str> {
+    text.split(',').map(str::trim).filter(|item| !item.is_empty()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn can_parse_meta() {
+        let tokens = lex("[meta]\n\
+            title = \"Amazing Grace\"\n\
+            authors = \"John Newton, Edwin Excell\"\n\
+            ccli = 22025\n\
+            tempo = 72\n\
+            tags = \"hymn, grace\"");
+        let meta = Meta::from_ast(&parse(&tokens));
+        assert_eq!(meta.title, Some("Amazing Grace"));
+        assert_eq!(meta.authors, ["John Newton", "Edwin Excell"]);
+        assert_eq!(meta.ccli, Some("22025"));
+        assert_eq!(meta.tempo, Some(72.0));
+        assert_eq!(meta.tags, ["hymn", "grace"]);
+        assert_eq!(meta.author(), "John Newton, Edwin Excell");
+    }
+}
@@ -0,0 +1,23 @@
+use crate::AstNode;
+use crate::Node;
+use crate::AST;
+
+impl Node {
+    /// Whether this is a `[notes]` block, meant for the people running the
+    /// service and never drawn for the audience.
+    pub fn is_notes(&self) -> bool {
+        self.kind == "notes"
+    }
+}
+
+/// The bodies of the `[notes]` blocks among `blocks`, in order.
+pub fn notes(ast: &AST, blocks: &[AstNode]) -> Vec<&'static str> {
+    blocks
+        .iter()
+        .filter_map(|block| match block {
+            &AstNode::Node(id) if ast[id].is_notes() => Some(ast[id].text().trim()),
+            _ => None,
+        })
+        .filter(|text| !text.is_empty())
+        .collect()
+}
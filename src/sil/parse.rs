@@ -176,7 +176,13 @@ impl Node {
     }
 
     pub fn text(&self) -> &'static str {
-        return consolidate_tokens_into_string(self.body());
+        return self.indented_text().trim_start_matches(' ');
+    }
+
+    /// The body with the indentation of its first line, for bodies where
+    /// indentation means something, like nested lists.
+    pub fn indented_text(&self) -> &'static str {
+        consolidate_tokens_into_string(self.body())
     }
 }
 impl PartialEq for Node {
@@ -206,8 +212,11 @@ impl Attribute {
         unsafe { &*self.value } // Tokens are expected to outlive Attribute.
     }
 
-    pub fn value_text(&self) -> &str {
-        return consolidate_tokens_into_string(self.value());
+    pub fn value_text(&self) -> &'static str {
+        return match self.value() {
+            [token] => token.text(),
+            tokens => consolidate_tokens_into_string(tokens),
+        };
     }
 
     pub fn name(&self) -> &str {
@@ -232,6 +241,10 @@ impl Debug for Attribute {
 pub fn parse(complete_tokens: &[Token]) -> AST {
     let mut ast = AST::new();
 
+    // Nodes point into `ast.attributes`, so it must never reallocate while
+    // parsing. Every attribute consumes at least three tokens.
+    ast.attributes.reserve(complete_tokens.len() / 3);
+
     let mut tokens = complete_tokens;
     while !tokens.is_empty() {
         match tokens {
@@ -461,6 +474,24 @@ unsafe impl Sync for Attribute {}
 #[cfg(test)]
 mod tests {
     use crate::parse::*;
+    use crate::lex;
+
+    #[test]
+    fn attributes_stay_put_while_parsing() {
+        // Enough attributes to grow `ast.attributes` several times over if
+        // it weren't reserved up front.
+        let tokens = lex("[text] a = 1 b = \"2\" c = 3\n".repeat(64).leak());
+        let ast = parse(&tokens);
+        let buffer = ast.attributes.as_ptr_range();
+        for block in ast.view() {
+            let AstNode::Node(id) = block else {
+                panic!("unexpected garbage");
+            };
+            let attributes = ast[*id].attributes;
+            assert!(buffer.contains(&attributes.cast::<Attribute>()));
+            assert_eq!(ast[*id].attributes()[1].value_text(), "2");
+        }
+    }
 
     #[test]
     fn can_parse_block() {
@@ -0,0 +1,101 @@
+use crate::Node;
+
+/// Names a `{placeholder}` in a body can take.
+pub const PLACEHOLDERS: [&str; 7] = [
+    "date",
+    "time",
+    "song.title",
+    "song.author",
+    "slide.index",
+    "slide.count",
+    "service.name",
+];
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Segment<'a> {
+    Text(&'a str),
+    /// The name between the braces.
+    Placeholder(&'a str),
+}
+
+/// Splits body text into plain text and `{name}` placeholders. Braces
+/// around anything but a name are left as text, and `{{` and `}}` stand
+/// for a single brace.
+pub fn split_placeholders(text: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut start = 0;
+    let mut rest = text;
+    while let Some(index) = rest.find(['{', '}']) {
+        let offset = text.len() - rest.len();
+        let brace = &rest[index..];
+        if brace.starts_with("{{") || brace.starts_with("}}") {
+            push_text(&mut segments, &text[start..offset + index + 1]);
+            start = offset + index + 2;
+            rest = &rest[index + 2..];
+            continue;
+        }
+        let name = brace[1..].find('}').map(|end| &brace[1..end + 1]);
+        match name {
+            Some(name) if brace.starts_with('{') && is_name(name) => {
+                push_text(&mut segments, &text[start..offset + index]);
+                segments.push(Segment::Placeholder(name));
+                start = offset + index + name.len() + 2;
+                rest = &rest[index + name.len() + 2..];
+            }
+            _ => rest = &rest[index + 1..],
+        }
+    }
+    push_text(&mut segments, &text[start..]);
+    segments
+}
+
+fn push_text<'a>(segments: &mut Vec<Segment<'a>>, text: &'a str) {
+    if !text.is_empty() {
+        segments.push(Segment::Text(text));
+    }
+}
+
+fn is_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_')
+}
+
+impl Node {
+    /// Names of the placeholders in the body, known or not.
+    pub fn placeholders(&self) -> impl Iterator<Item = &'static str> {
+        split_placeholders(self.text()).into_iter().filter_map(|segment| match segment {
+            Segment::Placeholder(name) => Some(name),
+            Segment::Text(_) => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::placeholder::*;
+
+    #[test]
+    fn can_split_placeholders() {
+        assert_eq!(
+            split_placeholders("{slide.index}/{slide.count} of {service.name}"),
+            [
+                Segment::Placeholder("slide.index"),
+                Segment::Text("/"),
+                Segment::Placeholder("slide.count"),
+                Segment::Text(" of "),
+                Segment::Placeholder("service.name"),
+            ]
+        );
+    }
+
+    #[test]
+    fn leaves_other_braces_alone() {
+        assert_eq!(
+            split_placeholders("{{date}} { not one } {"),
+            [
+                Segment::Text("{"),
+                Segment::Text("date}"),
+                Segment::Text(" { not one } {"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,187 @@
+use crate::AstNode;
+use crate::Node;
+use crate::AST;
+
+/// The part of a song a section is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SectionKind {
+    Verse,
+    Chorus,
+    PreChorus,
+    Bridge,
+    Ending,
+    /// Anything else, like an intro or an instrumental.
+    Other,
+}
+
+impl SectionKind {
+    pub fn from(s: &str) -> Option<Self> {
+        match s {
+            "verse" => Some(SectionKind::Verse),
+            "chorus" | "refrain" => Some(SectionKind::Chorus),
+            "pre_chorus" | "pre-chorus" | "prechorus" => Some(SectionKind::PreChorus),
+            "bridge" => Some(SectionKind::Bridge),
+            "ending" | "outro" | "tag" => Some(SectionKind::Ending),
+            _ => None,
+        }
+    }
+
+    /// A letter or two for tight spaces, like the `V` of `V2`.
+    pub fn abbreviation(self) -> &'static str {
+        match self {
+            SectionKind::Verse => "V",
+            SectionKind::Chorus => "C",
+            SectionKind::PreChorus => "P",
+            SectionKind::Bridge => "B",
+            SectionKind::Ending => "E",
+            SectionKind::Other => "",
+        }
+    }
+}
+
+/// A labeled part of a song, like `verse 2` or `chorus`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Section {
+    pub kind: SectionKind,
+    pub number: Option<u32>,
+    /// The label as written.
+    pub label: &'static str,
+}
+
+impl Section {
+    /// Reads labels like `verse 2`, `Chorus` or `bridge2`. Labels of other
+    /// kinds are kept as they are.
+    pub fn from(label: &'static str) -> Option<Self> {
+        let label = label.trim();
+        if label.is_empty() {
+            return None;
+        }
+        let name = label.trim_end_matches(|c: char| c.is_ascii_digit());
+        let number = label[name.len()..].parse().ok();
+        let kind = SectionKind::from(name.trim().to_ascii_lowercase().as_str());
+        Some(Self {
+            kind: kind.unwrap_or(SectionKind::Other),
+            number: kind.and(number),
+            label,
+        })
+    }
+
+    /// The label shortened, like `V2`, or as written for other kinds.
+    pub fn short_label(&self) -> String {
+        match (self.kind, self.number) {
+            (SectionKind::Other, _) => self.label.to_owned(),
+            (kind, Some(number)) => format!("{}{number}", kind.abbreviation()),
+            (kind, None) => kind.abbreviation().to_owned(),
+        }
+    }
+}
+
+impl Node {
+    /// Whether this block is about the deck rather than drawn on a slide,
+    /// like metadata, notes for the operator and presentation settings.
+    pub fn is_hidden(&self) -> bool {
+        self.kind == "meta" || self.kind == "presentation" || self.is_notes()
+    }
+}
+
+/// Where each labeled section of a file starts. A section runs until the
+/// next slide with a label.
+#[derive(Debug, Default, PartialEq)]
+pub struct SectionIndex(Vec<(usize, Section)>);
+
+impl SectionIndex {
+    /// Reads the `section` of each `[slide]` block. Slides are counted the
+    /// way decks split them: one at each `[slide]` block, after one for
+    /// anything but hidden blocks above the first.
+    pub fn from_ast(ast: &AST) -> Self {
+        let blocks = ast.view();
+        let node = |block: &AstNode| match block {
+            &AstNode::Node(id) => Some(&ast[id]),
+            _ => None,
+        };
+        let is_slide = |block: &AstNode| node(block).is_some_and(|node| node.kind == "slide");
+        let is_hidden = |block: &AstNode| node(block).is_some_and(Node::is_hidden);
+        let first = blocks.iter().position(is_slide).unwrap_or(blocks.len());
+        let leading = first == blocks.len() || !blocks[..first].iter().all(is_hidden);
+        let slides = blocks[first..].iter().filter(|block| is_slide(block)).filter_map(node);
+        SectionIndex(
+            slides
+                .enumerate()
+                .filter_map(|(index, slide)| {
+                    // The last `section` counts, as with other attributes.
+                    let label = slide.attributes().iter().rev().find(|attribute| attribute.name() == "section")?;
+                    Some((index + leading as usize, Section::from(label.value_text())?))
+                })
+                .collect(),
+        )
+    }
+
+    /// Sections with the slide they start on.
+    pub fn view(&self) -> &[(usize, Section)] {
+        self.0.as_slice()
+    }
+
+    /// The section slide `index` is in.
+    pub fn containing(&self, index: usize) -> Option<&Section> {
+        self.0.iter().rev().find(|(start, _)| *start <= index).map(|(_, section)| section)
+    }
+
+    /// The slide to jump to for a section of `kind`. With a number, that's
+    /// the section labeled with it, or else the nth of its kind. Without,
+    /// it's the next section of the kind after slide `current`, wrapping
+    /// around.
+    pub fn find(&self, kind: SectionKind, number: Option<u32>, current: usize) -> Option<usize> {
+        let mut of_kind = self.0.iter().filter(|(_, section)| section.kind == kind);
+        let Some(number) = number else {
+            let mut starts = of_kind.map(|(start, _)| *start);
+            let first = starts.clone().next();
+            return starts.find(|start| *start > current).or(first);
+        };
+        of_kind
+            .clone()
+            .find(|(_, section)| section.number == Some(number))
+            .or_else(|| of_kind.nth(number.checked_sub(1)? as usize))
+            .map(|(start, _)| *start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::section::*;
+    use crate::*;
+
+    #[test]
+    fn can_parse_sections() {
+        let verse = Section::from("Verse 2").unwrap();
+        assert_eq!(verse.kind, SectionKind::Verse);
+        assert_eq!(verse.number, Some(2));
+        assert_eq!(verse.short_label(), "V2");
+        assert_eq!(Section::from("pre-chorus").unwrap().short_label(), "P");
+        assert_eq!(Section::from("Intro").unwrap().kind, SectionKind::Other);
+        assert_eq!(Section::from(" "), None);
+    }
+
+    #[test]
+    fn finds_sections() {
+        let tokens = lex("[slide] section = \"verse 1\"\n[slide]\n\
+            [slide] section = \"chorus\"\n\
+            [slide] section = \"verse 2\"\n\
+            [slide] section = \"chorus\"");
+        let sections = SectionIndex::from_ast(&parse(&tokens));
+        assert_eq!(sections.view().len(), 4);
+        assert_eq!(sections.containing(1).map(|section| section.label), Some("verse 1"));
+        assert_eq!(sections.find(SectionKind::Verse, Some(2), 0), Some(3));
+        assert_eq!(sections.find(SectionKind::Chorus, None, 2), Some(4));
+        assert_eq!(sections.find(SectionKind::Chorus, None, 4), Some(2));
+        assert_eq!(sections.find(SectionKind::Chorus, Some(2), 0), Some(4));
+        assert_eq!(sections.find(SectionKind::Bridge, None, 0), None);
+    }
+
+    #[test]
+    fn counts_slides_before_the_first() {
+        let tokens = lex("[meta] title = \"Song\"\n[notes]\nSlow\n[slide] section = \"verse\"");
+        assert_eq!(SectionIndex::from_ast(&parse(&tokens)).view()[0].0, 0);
+        let tokens = lex("[title]\nSong\n[slide] section = \"verse\"");
+        assert_eq!(SectionIndex::from_ast(&parse(&tokens)).view()[0].0, 1);
+    }
+}
@@ -1,23 +1,44 @@
 mod lex;
+mod meta;
+mod notes;
 mod parse;
+mod placeholder;
+mod section;
 mod typecheck;
 
 pub use lex::*;
+pub use meta::*;
+pub use notes::*;
 pub use parse::*;
+pub use placeholder::*;
+pub use section::*;
 pub use typecheck::*;
 
 pub struct SilFile {
     pub content: &'static str,
+    pub meta: Meta,
+    /// Placeholders in bodies that don't name anything, shown as typed.
+    pub unknown_placeholders: Vec<&'static str>,
+    pub sections: SectionIndex,
 }
 
 impl SilFile {
     pub fn from(content: &'static str) -> Option<Self> {
         let tokens = lex(content);
         let ast = parse(&tokens);
-        let _typechecked_ast = typecheck(&ast);
 
-        return Some(SilFile {
-            content
-        });
+        return Some(SilFile::from_ast(content, &ast));
+    }
+
+    /// Reads a file already parsed into `ast`.
+    pub fn from_ast(content: &'static str, ast: &AST) -> Self {
+        let typechecked_ast = typecheck(ast);
+
+        SilFile {
+            content,
+            meta: Meta::from_ast(ast),
+            unknown_placeholders: typechecked_ast.unknown_placeholders().collect(),
+            sections: SectionIndex::from_ast(ast),
+        }
     }
 }
@@ -1,6 +1,7 @@
 use crate::AstNode;
 use crate::Node;
 use crate::AST;
+use crate::PLACEHOLDERS;
 
 pub struct CheckedAst {
     nodes: Vec<CheckedNode>,
@@ -14,6 +15,11 @@ impl CheckedAst {
             callback(node);
         }
     }
+
+    /// Placeholders in bodies that don't name anything.
+    pub fn unknown_placeholders(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.nodes.iter().flat_map(|node| node.unknown_placeholders.iter().copied())
+    }
 }
 
 struct Types {}
@@ -22,11 +28,12 @@ pub fn typecheck(ast: &AST) -> CheckedAst {
     let mut view = ast.view();
 
     let types = Types {};
+    let mut nodes = Vec::new();
 
     while !view.is_empty() {
         match view {
             [AstNode::Node(id), ..] => {
-                let _checked_node = typecheck_node(&types, &ast[*id]);
+                nodes.push(typecheck_node(&types, &ast[*id]));
                 view = &view[1..];
             }
             [AstNode::Garbage(id), ..] => {
@@ -39,18 +46,45 @@ pub fn typecheck(ast: &AST) -> CheckedAst {
     }
 
     CheckedAst {
-        nodes: Vec::new()
+        nodes
     }
 }
 
-struct CheckedNode {}
+struct CheckedNode {
+    unknown_placeholders: Vec<&'static str>,
+}
 fn typecheck_node(types: &Types, node: &Node) -> CheckedNode {
     match node {
         Node { kind: "title", .. } => typecheck_title(types, node),
-        _ => todo!(),
+        _ => typecheck_body(node),
+    }
+}
+
+fn typecheck_title(_types: &Types, node: &Node) -> CheckedNode {
+    typecheck_body(node)
+}
+
+fn typecheck_body(node: &Node) -> CheckedNode {
+    CheckedNode {
+        unknown_placeholders: node.placeholders().filter(|name| !PLACEHOLDERS.contains(name)).collect(),
     }
 }
 
-fn typecheck_title(_types: &Types, _node: &Node) -> CheckedNode {
-    CheckedNode {}
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn finds_unknown_placeholders() {
+        let tokens = lex("[text]\n{song.title} by {song.composer}\n\n[title]\n{date}");
+        let ast = parse(&tokens);
+        let checked = typecheck(&ast);
+        assert_eq!(checked.unknown_placeholders().collect::<Vec<_>>(), ["song.composer"]);
+    }
+
+    #[test]
+    fn files_keep_unknown_placeholders() {
+        let file = SilFile::from("[text]\n{slide.index} of {slide.total}").unwrap();
+        assert_eq!(file.unknown_placeholders, ["slide.total"]);
+    }
 }
@@ -0,0 +1,112 @@
+use std::ops::Range;
+
+use crate::{Layer, Slide};
+
+/// How a slide's content is revealed one part at a time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Build {
+    /// Every line of text, list item and table row is a part of its own.
+    Line,
+    /// Every layer is a part of its own.
+    Block,
+}
+
+impl Build {
+    pub fn from(s: &str) -> Option<Self> {
+        match s {
+            "line" => Some(Build::Line),
+            "block" => Some(Build::Block),
+            _ => None,
+        }
+    }
+}
+
+/// How a revealed part enters.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum BuildEffect {
+    #[default]
+    Fade,
+    /// Fades in while rising into place.
+    Rise,
+}
+
+impl BuildEffect {
+    pub fn from(s: &str) -> Option<Self> {
+        match s {
+            "fade" => Some(BuildEffect::Fade),
+            "rise" => Some(BuildEffect::Rise),
+            _ => None,
+        }
+    }
+}
+
+impl Slide {
+    /// The parts each layer takes up, by layer index. A layer with more
+    /// than one part reveals one line per part. Backgrounds take up none
+    /// and are always shown.
+    pub fn build_parts(&self) -> Vec<Range<usize>> {
+        let mut next = 0;
+        let mut parts = Vec::new();
+        for layer in self.layers.view() {
+            let count = match (self.build, layer) {
+                (_, Layer::Background(_)) => 0,
+                (None, _) => {
+                    parts.push(0..1);
+                    continue;
+                }
+                (Some(Build::Line), Layer::Text(layer)) => line_count(layer.text),
+                (Some(Build::Line), Layer::Title(layer)) => line_count(layer.text),
+                (Some(Build::Line), Layer::List(layer)) => layer.items.len().max(1),
+                (Some(Build::Line), Layer::Table(layer)) => layer.rows.len().max(1),
+                (Some(_), _) => 1,
+            };
+            parts.push(next..next + count);
+            next += count;
+        }
+        parts
+    }
+
+    /// How many times "next" reveals something before leaving the slide,
+    /// counting the first part shown on entering it.
+    pub fn part_count(&self) -> usize {
+        self.build_parts().iter().map(|parts| parts.end).max().unwrap_or(0).max(1)
+    }
+}
+
+fn line_count(text: &str) -> usize {
+    text.lines().count().max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn slide(build: Option<Build>) -> Slide {
+        let layers = vec![
+            Layer::Background(BackgroundLayer::default()),
+            Layer::Title(TitleLayer {
+                text: "Title",
+                ..TitleLayer::default()
+            }),
+            Layer::Text(TextLayer {
+                text: "One\nTwo\nThree",
+                ..TextLayer::default()
+            }),
+            Layer::Image(ImageLayer::default()),
+        ];
+        Slide {
+            layers: Layers(layers),
+            build,
+            ..Slide::default()
+        }
+    }
+
+    #[test]
+    fn splits_builds_into_parts() {
+        assert_eq!(slide(None).build_parts(), [0..0, 0..1, 0..1, 0..1]);
+        assert_eq!(slide(None).part_count(), 1);
+        assert_eq!(slide(Some(Build::Block)).build_parts(), [0..0, 0..1, 1..2, 2..3]);
+        assert_eq!(slide(Some(Build::Line)).build_parts(), [0..0, 0..1, 1..4, 4..5]);
+        assert_eq!(slide(Some(Build::Line)).part_count(), 5);
+    }
+}
@@ -0,0 +1,75 @@
+use sil::Attribute;
+
+/// How a layer is combined with the layers below it. Layers with a higher
+/// `z` are drawn on top, layers with the same `z` in source order.
+/// Backgrounds always go below everything else.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Composite {
+    pub z: i32,
+    pub opacity: f32,
+    /// Hidden layers keep their place, so toggling one doesn't move the
+    /// others around.
+    pub visible: bool,
+}
+
+impl Default for Composite {
+    fn default() -> Self {
+        Self {
+            z: 0,
+            opacity: 1.0,
+            visible: true,
+        }
+    }
+}
+
+impl Composite {
+    pub fn parse(&mut self, attribute: &Attribute) {
+        let text = attribute.value_text();
+        match attribute.name() {
+            "z" => self.z = text.parse().unwrap_or(self.z),
+            "opacity" => {
+                // `clamp` lets NaN through, which would tint every color.
+                let opacity = text.parse::<f32>().ok().filter(|opacity| opacity.is_finite());
+                self.opacity = opacity.unwrap_or(1.0).clamp(0.0, 1.0);
+            }
+            "visible" => self.visible = parse_bool(text).unwrap_or(self.visible),
+            _ => {
+                // FIXME: Add garbage attribute
+            }
+        }
+    }
+}
+
+/// Reads `true`, `yes` and `1`, or `false`, `no` and `0`.
+pub fn parse_bool(s: &str) -> Option<bool> {
+    match s {
+        "true" | "yes" | "1" => Some(true),
+        "false" | "no" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::composite::*;
+    use sil::Token;
+
+    #[test]
+    fn ignores_nan_opacity() {
+        let mut composite = Composite::default();
+        for (value, opacity) in [("0.5", 0.5), ("NaN", 1.0), ("2", 1.0)] {
+            composite.parse(&Attribute {
+                name: "opacity",
+                value: &[Token::Number(value)],
+            });
+            assert_eq!(composite.opacity, opacity);
+        }
+    }
+
+    #[test]
+    fn can_parse_booleans() {
+        assert_eq!(parse_bool("false"), Some(false));
+        assert_eq!(parse_bool("1"), Some(true));
+        assert_eq!(parse_bool("maybe"), None);
+    }
+}
@@ -0,0 +1,142 @@
+use sil::Attribute;
+
+use crate::parse_color;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Outline {
+    pub width: f32,
+    pub color: u32,
+}
+
+impl Outline {
+    /// Parses `width [color]`, e.g. `4 #000000`.
+    pub fn from(s: &str) -> Option<Self> {
+        let mut parts = s.split_whitespace();
+        let width = parts.next()?.parse().ok()?;
+        let color = parts.next().map_or(Some(0x000000ff), parse_color)?;
+        Some(Self { width, color })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Shadow {
+    pub x: f32,
+    pub y: f32,
+    pub blur: f32,
+    pub color: u32,
+}
+
+impl Shadow {
+    /// Parses `x y [blur] [color]`, e.g. `4 4 8 #000000c0`.
+    pub fn from(s: &str) -> Option<Self> {
+        let mut parts = s.split_whitespace().peekable();
+        let x = parts.next()?.parse().ok()?;
+        let y = parts.next()?.parse().ok()?;
+        let blur = match parts.peek() {
+            Some(part) if !part.starts_with('#') => parts.next()?.parse().ok()?,
+            _ => 0.0,
+        };
+        let color = parts.next().map_or(Some(0x000000c0), parse_color)?;
+        Some(Self { x, y, blur, color })
+    }
+}
+
+/// A rounded rectangle drawn behind the text.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Backdrop {
+    pub color: u32,
+    pub padding: f32,
+    pub radius: f32,
+}
+
+impl Default for Backdrop {
+    fn default() -> Self {
+        Self {
+            color: 0x00000080,
+            padding: 16.0,
+            radius: 8.0,
+        }
+    }
+}
+
+/// Effects keeping text legible on busy backgrounds. Sizes are in canvas
+/// units.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct TextEffects {
+    pub outline: Option<Outline>,
+    pub shadow: Option<Shadow>,
+    pub backdrop: Option<Backdrop>,
+}
+
+impl TextEffects {
+    pub fn parse(&mut self, attribute: &Attribute) {
+        let text = attribute.value_text();
+        match attribute.name() {
+            "outline" => self.outline = Outline::from(text),
+            "shadow" => self.shadow = Shadow::from(text),
+            "backdrop" => {
+                self.backdrop = parse_color(text).map(|color| Backdrop {
+                    color,
+                    ..self.backdrop.unwrap_or_default()
+                })
+            }
+            "backdrop_padding" => {
+                let backdrop = self.backdrop.get_or_insert_with(Backdrop::default);
+                backdrop.padding = text.parse().unwrap_or(backdrop.padding);
+            }
+            "backdrop_radius" => {
+                let backdrop = self.backdrop.get_or_insert_with(Backdrop::default);
+                backdrop.radius = text.parse().unwrap_or(backdrop.radius);
+            }
+            _ => {
+                // FIXME: Add garbage attribute
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::effects::*;
+
+    #[test]
+    fn can_parse_outline() {
+        assert_eq!(
+            Outline::from("4"),
+            Some(Outline {
+                width: 4.0,
+                color: 0x000000ff
+            })
+        );
+        assert_eq!(
+            Outline::from("2.5 #ffffff"),
+            Some(Outline {
+                width: 2.5,
+                color: 0xffffffff
+            })
+        );
+        assert_eq!(Outline::from("#ffffff"), None);
+    }
+
+    #[test]
+    fn can_parse_shadow() {
+        assert_eq!(
+            Shadow::from("4 6 #102030"),
+            Some(Shadow {
+                x: 4.0,
+                y: 6.0,
+                blur: 0.0,
+                color: 0x102030ff
+            })
+        );
+        assert_eq!(
+            Shadow::from("4 6 8"),
+            Some(Shadow {
+                x: 4.0,
+                y: 6.0,
+                blur: 8.0,
+                color: 0x000000c0
+            })
+        );
+    }
+}
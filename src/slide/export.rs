@@ -0,0 +1,47 @@
+use crate::{Deck, Layer};
+
+/// The text of a deck, a paragraph per slide, like for a lyrics sheet.
+/// Notes are for the people running the service, so they're only put in
+/// when `notes` asks for them, after the slide they go with.
+pub fn export_text(deck: &Deck, notes: bool) -> String {
+    let mut paragraphs = Vec::new();
+    for slide in deck.view() {
+        let mut lines = Vec::new();
+        if let Some(section) = slide.section {
+            lines.push(format!("[{}]", section.label));
+        }
+        for layer in slide.layers.view() {
+            match layer {
+                Layer::Title(title) => lines.push(title.text.trim().to_owned()),
+                Layer::Text(text) => lines.push(text.text.trim().to_owned()),
+                _ => {}
+            }
+        }
+        if notes {
+            lines.extend(slide.notes.iter().map(|note| format!("Notes: {}", note.trim())));
+        }
+        lines.retain(|line| !line.is_empty());
+        if !lines.is_empty() {
+            paragraphs.push(lines.join("\n"));
+        }
+    }
+    paragraphs.join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::export::*;
+    use sil::*;
+
+    #[test]
+    fn exports_notes_on_request() {
+        let tokens = lex("[slide] section = \"verse 1\"\n[text]\nAmazing grace\n[notes]\nPastor prays here\n\
+            [slide]\n[text]\nHow sweet the sound");
+        let deck = Deck::from(&parse(&tokens));
+        assert_eq!(export_text(&deck, false), "[verse 1]\nAmazing grace\n\nHow sweet the sound");
+        assert_eq!(
+            export_text(&deck, true),
+            "[verse 1]\nAmazing grace\nNotes: Pastor prays here\n\nHow sweet the sound"
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use sil::{Meta, Node};
+
+use crate::parse_color;
+
+/// Which slide of a song gets the footer.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum FooterSlide {
+    First,
+    #[default]
+    Last,
+}
+
+impl FooterSlide {
+    pub fn from(s: &str) -> Option<Self> {
+        match s {
+            "first" => Some(FooterSlide::First),
+            "last" => Some(FooterSlide::Last),
+            _ => None,
+        }
+    }
+}
+
+/// The copyright and CCLI notice drawn along the bottom of one slide of
+/// every song, from its `[meta]` block. Themes turn it on with a `[footer]`
+/// block. Sizes are in canvas units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Footer {
+    pub on: FooterSlide,
+    /// The CCLI license number the songs are shown under.
+    pub license: Option<&'static str>,
+    pub font_size: f32,
+    pub color: Option<u32>,
+    /// Space below the footer.
+    pub margin: f32,
+}
+
+impl Default for Footer {
+    fn default() -> Self {
+        Self {
+            on: FooterSlide::default(),
+            license: None,
+            font_size: 20.0,
+            color: None,
+            margin: 32.0,
+        }
+    }
+}
+
+impl From<&Node> for Footer {
+    fn from(value: &Node) -> Self {
+        let mut res = Self::default();
+        for attribute in value.attributes() {
+            let text = attribute.value_text();
+            match attribute.name() {
+                "on" => res.on = FooterSlide::from(text).unwrap_or(res.on),
+                "license" => res.license = Some(text),
+                "font_size" => res.font_size = text.parse::<f32>().unwrap_or(res.font_size).max(1.0),
+                "color" => res.color = parse_color(text),
+                "margin" => res.margin = text.parse().unwrap_or(res.margin),
+                _ => {
+                    // FIXME: Add garbage attribute
+                }
+            }
+        }
+        res
+    }
+}
+
+impl Footer {
+    /// Whether the footer goes on slide `index` of `count`.
+    pub fn is_on(&self, index: usize, count: usize) -> bool {
+        match self.on {
+            FooterSlide::First => index == 0,
+            FooterSlide::Last => index + 1 == count,
+        }
+    }
+
+    /// The notice for a song, or `None` when it has neither a copyright nor
+    /// a CCLI number.
+    pub fn text(&self, meta: &Meta) -> Option<String> {
+        if meta.copyright.is_none() && meta.ccli.is_none() {
+            return None;
+        }
+        let mut lines = Vec::new();
+        match (meta.title, meta.authors.is_empty()) {
+            (Some(title), false) => lines.push(format!("{title} – {}", meta.author())),
+            (Some(title), true) => lines.push(title.to_owned()),
+            (None, false) => lines.push(meta.author()),
+            (None, true) => {}
+        }
+        if let Some(copyright) = meta.copyright {
+            lines.push(format!("© {copyright}"));
+        }
+        match (meta.ccli, self.license) {
+            (Some(ccli), Some(license)) => lines.push(format!("CCLI Song # {ccli} · CCLI License # {license}")),
+            (Some(ccli), None) => lines.push(format!("CCLI Song # {ccli}")),
+            (None, _) => {}
+        }
+        Some(lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::footer::*;
+
+    #[test]
+    fn lists_copyright_and_ccli() {
+        let meta = Meta {
+            title: Some("Amazing Grace"),
+            authors: vec!["John Newton"],
+            ccli: Some("22025"),
+            copyright: Some("Public Domain"),
+            ..Meta::default()
+        };
+        let footer = Footer {
+            license: Some("1234567"),
+            ..Footer::default()
+        };
+        assert_eq!(
+            footer.text(&meta).unwrap(),
+            "Amazing Grace – John Newton\n© Public Domain\nCCLI Song # 22025 · CCLI License # 1234567"
+        );
+        assert_eq!(footer.text(&Meta::default()), None);
+        assert!(footer.is_on(2, 3));
+        assert!(!footer.is_on(0, 3));
+    }
+}
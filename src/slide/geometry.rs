@@ -0,0 +1,139 @@
+use sil::Attribute;
+
+/// A distance in canvas units, or a percentage of the canvas.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Length {
+    Units(f32),
+    Percent(f32),
+}
+
+impl Default for Length {
+    fn default() -> Self {
+        Length::Units(0.0)
+    }
+}
+
+impl Length {
+    pub fn from(s: &str) -> Option<Self> {
+        match s.strip_suffix('%') {
+            Some(percent) => percent.trim().parse().ok().map(Length::Percent),
+            None => s.trim().parse().ok().map(Length::Units),
+        }
+    }
+
+    pub fn resolve(&self, total: f32) -> f32 {
+        match *self {
+            Length::Units(units) => units,
+            Length::Percent(percent) => total * percent / 100.0,
+        }
+    }
+}
+
+/// Which point of a layer's box `x` and `y` refer to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Anchor {
+    pub fn from(s: &str) -> Option<Self> {
+        match s {
+            "top_left" => Some(Anchor::TopLeft),
+            "top" => Some(Anchor::Top),
+            "top_right" => Some(Anchor::TopRight),
+            "left" => Some(Anchor::Left),
+            "center" => Some(Anchor::Center),
+            "right" => Some(Anchor::Right),
+            "bottom_left" => Some(Anchor::BottomLeft),
+            "bottom" => Some(Anchor::Bottom),
+            "bottom_right" => Some(Anchor::BottomRight),
+            _ => None,
+        }
+    }
+
+    /// Position of the anchor within a box, from `[0, 0]` (top left) to
+    /// `[1, 1]` (bottom right).
+    pub fn fraction(&self) -> [f32; 2] {
+        match self {
+            Anchor::TopLeft => [0.0, 0.0],
+            Anchor::Top => [0.5, 0.0],
+            Anchor::TopRight => [1.0, 0.0],
+            Anchor::Left => [0.0, 0.5],
+            Anchor::Center => [0.5, 0.5],
+            Anchor::Right => [1.0, 0.5],
+            Anchor::BottomLeft => [0.0, 1.0],
+            Anchor::Bottom => [0.5, 1.0],
+            Anchor::BottomRight => [1.0, 1.0],
+        }
+    }
+}
+
+/// Where a layer goes on the canvas. Layers without `x` and `y` are stacked
+/// from the top of the canvas down.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Geometry {
+    pub x: Option<Length>,
+    pub y: Option<Length>,
+    pub width: Option<Length>,
+    pub height: Option<Length>,
+    pub padding: Length,
+    pub margin: Length,
+    pub anchor: Anchor,
+}
+
+impl Default for Geometry {
+    fn default() -> Self {
+        Self {
+            x: None,
+            y: None,
+            width: None,
+            height: None,
+            padding: Length::default(),
+            margin: Length::default(),
+            anchor: Anchor::TopLeft,
+        }
+    }
+}
+
+impl Geometry {
+    pub fn is_positioned(&self) -> bool {
+        self.x.is_some() || self.y.is_some()
+    }
+
+    pub fn parse(&mut self, attribute: &Attribute) {
+        let text = attribute.value_text();
+        match attribute.name() {
+            "x" => self.x = Length::from(text),
+            "y" => self.y = Length::from(text),
+            "width" => self.width = Length::from(text),
+            "height" => self.height = Length::from(text),
+            "padding" => self.padding = Length::from(text).unwrap_or_default(),
+            "margin" => self.margin = Length::from(text).unwrap_or_default(),
+            "anchor" => self.anchor = Anchor::from(text).unwrap_or(Anchor::TopLeft),
+            _ => {
+                // FIXME: Add garbage attribute
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::*;
+
+    #[test]
+    fn can_parse_lengths() {
+        assert_eq!(Length::from("120"), Some(Length::Units(120.0)));
+        assert_eq!(Length::from("12.5%"), Some(Length::Percent(12.5)));
+        assert_eq!(Length::from("wide"), None);
+        assert_eq!(Length::Percent(25.0).resolve(1920.0), 480.0);
+    }
+}
@@ -0,0 +1,209 @@
+use sil::{Attribute, Node};
+
+use crate::{parse_color, parse_size, Composite, Geometry};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ListMarker {
+    /// Bullets change shape with the nesting depth.
+    Bullet,
+    Number,
+    Letter,
+    /// The same text in front of every item.
+    Custom(&'static str),
+    None,
+}
+
+impl ListMarker {
+    pub fn from(s: &'static str) -> Self {
+        match s {
+            "bullet" => ListMarker::Bullet,
+            "number" => ListMarker::Number,
+            "letter" => ListMarker::Letter,
+            "none" => ListMarker::None,
+            _ => ListMarker::Custom(s),
+        }
+    }
+
+    /// The marker in front of the `count`th item (counting from 1) at
+    /// `depth`.
+    pub fn text(&self, depth: usize, count: usize) -> String {
+        match self {
+            ListMarker::Bullet => ["•", "◦", "▪"][depth % 3].to_owned(),
+            ListMarker::Number => format!("{count}."),
+            ListMarker::Letter => {
+                let letter = (b'a' + ((count.max(1) - 1) % 26) as u8) as char;
+                format!("{letter}.")
+            }
+            ListMarker::Custom(marker) => (*marker).to_owned(),
+            ListMarker::None => String::new(),
+        }
+    }
+}
+
+/// How lists look. Unset fields fall back to the theme's `[list]` block.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ListStyle {
+    pub marker: Option<ListMarker>,
+    pub font_size: Option<f32>,
+    pub color: Option<u32>,
+    pub marker_color: Option<u32>,
+    /// How far each level of nesting is indented, in canvas units.
+    pub indent: Option<f32>,
+}
+
+impl ListStyle {
+    pub fn parse(&mut self, attribute: &Attribute) {
+        let text = attribute.value_text();
+        match attribute.name() {
+            "marker" => self.marker = Some(ListMarker::from(text)),
+            "font_size" => self.font_size = parse_size(text, 1.0),
+            "color" => self.color = parse_color(text),
+            "marker_color" => self.marker_color = parse_color(text),
+            "indent" => self.indent = parse_size(text, 0.0),
+            _ => {
+                // FIXME: Add garbage attribute
+            }
+        }
+    }
+
+    pub fn or(self, fallback: ListStyle) -> ListStyle {
+        ListStyle {
+            marker: self.marker.or(fallback.marker),
+            font_size: self.font_size.or(fallback.font_size),
+            color: self.color.or(fallback.color),
+            marker_color: self.marker_color.or(fallback.marker_color),
+            indent: self.indent.or(fallback.indent),
+        }
+    }
+
+    pub fn marker(&self) -> ListMarker {
+        self.marker.unwrap_or(ListMarker::Bullet)
+    }
+
+    pub fn font_size(&self) -> f32 {
+        self.font_size.unwrap_or(40.0)
+    }
+
+    pub fn indent(&self) -> f32 {
+        self.indent.unwrap_or(self.font_size() * 1.5)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ListItem {
+    pub text: &'static str,
+    /// How many levels the item is nested.
+    pub depth: usize,
+}
+
+/// One item per line of the body. Indenting a line further than the one
+/// before it nests it.
+#[derive(Debug, Default, PartialEq)]
+pub struct ListLayer {
+    pub items: Vec<ListItem>,
+    pub style: ListStyle,
+    pub geometry: Geometry,
+    pub composite: Composite,
+}
+
+impl From<&Node> for ListLayer {
+    fn from(value: &Node) -> Self {
+        let mut res = Self {
+            items: parse_items(value.indented_text()),
+            ..Self::default()
+        };
+        for attribute in value.attributes() {
+            match attribute.name() {
+                "marker" | "font_size" | "color" | "marker_color" | "indent" => res.style.parse(attribute),
+                "z" | "opacity" | "visible" => res.composite.parse(attribute),
+                _ => res.geometry.parse(attribute),
+            }
+        }
+        res
+    }
+}
+
+fn indent_width(whitespace: &str) -> usize {
+    whitespace.chars().map(|c| if c == '\t' { 4 } else { 1 }).sum()
+}
+
+fn parse_items(text: &'static str) -> Vec<ListItem> {
+    let mut items = Vec::new();
+    // Indentation of every level the current item is nested in.
+    let mut indents: Vec<usize> = Vec::new();
+    for line in text.lines() {
+        let item = line.trim_start();
+        if item.is_empty() {
+            continue;
+        }
+        let indent = indent_width(&line[..line.len() - item.len()]);
+        while indents.last().is_some_and(|&last| last > indent) {
+            indents.pop();
+        }
+        if indents.last().is_none_or(|&last| last < indent) {
+            indents.push(indent);
+        }
+        items.push(ListItem {
+            text: item.trim_end(),
+            depth: indents.len() - 1,
+        });
+    }
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list::*;
+
+    #[test]
+    fn nests_items_by_indent() {
+        let items = parse_items("Songs\n  Opening\n  Closing\n    Encore\nPrayer\n\tNotes");
+        let depths: Vec<(&str, usize)> = items.iter().map(|item| (item.text, item.depth)).collect();
+        assert_eq!(
+            depths,
+            [
+                ("Songs", 0),
+                ("Opening", 1),
+                ("Closing", 1),
+                ("Encore", 2),
+                ("Prayer", 0),
+                ("Notes", 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_indent_of_first_item_from_source() {
+        let tokens = sil::lex("[list]\n    One\n    Two\n      Three");
+        let ast = sil::parse(&tokens);
+        let sil::AstNode::Node(id) = ast.view()[0] else {
+            panic!("expected a node");
+        };
+        let depths: Vec<usize> = ListLayer::from(&ast[id]).items.iter().map(|item| item.depth).collect();
+        assert_eq!(depths, [0, 0, 1]);
+    }
+
+    #[test]
+    fn bounds_style_sizes() {
+        let mut style = ListStyle::default();
+        for (name, value) in [("font_size", "NaN"), ("indent", "-8")] {
+            style.parse(&Attribute {
+                name,
+                value: &[sil::Token::Number(value)],
+            });
+        }
+        assert_eq!((style.font_size, style.indent), (None, Some(0.0)));
+        style.parse(&Attribute {
+            name: "font_size",
+            value: &[sil::Token::Number("0")],
+        });
+        assert_eq!(style.font_size, Some(1.0));
+    }
+
+    #[test]
+    fn numbers_and_letters_count_from_one() {
+        assert_eq!(ListMarker::Number.text(0, 3), "3.");
+        assert_eq!(ListMarker::Letter.text(1, 2), "b.");
+        assert_eq!(ListMarker::Bullet.text(1, 1), "◦");
+    }
+}
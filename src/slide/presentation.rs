@@ -0,0 +1,44 @@
+use sil::{AstNode, Node, AST};
+
+use crate::{parse_bool, parse_duration};
+
+/// How a deck plays on its own, from its `[presentation]` block, like an
+/// announcement loop before the service.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Presentation {
+    /// Starts over after the last slide.
+    pub looping: bool,
+    /// How long slides without a `duration` of their own show, in seconds.
+    pub duration: Option<f64>,
+}
+
+impl Presentation {
+    /// The settings of the last `[presentation]` block in `ast`.
+    pub fn from_ast(ast: &AST) -> Self {
+        ast.view()
+            .iter()
+            .rev()
+            .find_map(|block| match block {
+                &AstNode::Node(id) if ast[id].kind == "presentation" => Some(Presentation::from(&ast[id])),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl From<&Node> for Presentation {
+    fn from(value: &Node) -> Self {
+        let mut res = Self::default();
+        for attribute in value.attributes() {
+            let text = attribute.value_text();
+            match attribute.name() {
+                "loop" => res.looping = parse_bool(text).unwrap_or(res.looping),
+                "duration" => res.duration = parse_duration(text).filter(|duration| *duration > 0.0),
+                _ => {
+                    // FIXME: Add garbage attribute
+                }
+            }
+        }
+        res
+    }
+}
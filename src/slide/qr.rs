@@ -0,0 +1,159 @@
+use qrcodegen::{QrCode, QrCodeEcc};
+use sil::Node;
+
+use crate::{parse_color, Composite, Geometry, Length};
+
+// In modules. The standard asks for 4, more only shrinks the code.
+const MAX_QUIET_ZONE: usize = 32;
+
+/// How much of a code can be damaged and still scan.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ErrorCorrection {
+    Low,
+    Medium,
+    Quartile,
+    High,
+}
+
+impl ErrorCorrection {
+    pub fn from(s: &str) -> Option<Self> {
+        match s {
+            "low" => Some(ErrorCorrection::Low),
+            "medium" => Some(ErrorCorrection::Medium),
+            "quartile" => Some(ErrorCorrection::Quartile),
+            "high" => Some(ErrorCorrection::High),
+            _ => None,
+        }
+    }
+
+    fn ecc(&self) -> QrCodeEcc {
+        match self {
+            ErrorCorrection::Low => QrCodeEcc::Low,
+            ErrorCorrection::Medium => QrCodeEcc::Medium,
+            ErrorCorrection::Quartile => QrCodeEcc::Quartile,
+            ErrorCorrection::High => QrCodeEcc::High,
+        }
+    }
+}
+
+/// The modules of an encoded code, row by row, without the quiet zone.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QrModules {
+    pub size: usize,
+    pub dark: Vec<bool>,
+}
+
+impl QrModules {
+    pub fn encode(payload: &str, error_correction: ErrorCorrection) -> Option<Self> {
+        let code = QrCode::encode_text(payload, error_correction.ecc()).ok()?;
+        let size = code.size();
+        let dark = (0..size)
+            .flat_map(|y| (0..size).map(move |x| (x, y)))
+            .map(|(x, y)| code.get_module(x, y))
+            .collect();
+        Some(Self {
+            size: size as usize,
+            dark,
+        })
+    }
+
+    pub fn is_dark(&self, x: usize, y: usize) -> bool {
+        self.dark[y * self.size + x]
+    }
+}
+
+/// A QR code of the body, encoded when the slide is parsed.
+#[derive(Debug, PartialEq)]
+pub struct QrLayer {
+    pub payload: &'static str,
+    pub error_correction: ErrorCorrection,
+    /// `None` when the payload doesn't fit in a code.
+    pub modules: Option<QrModules>,
+    /// Blank modules around the code.
+    pub quiet_zone: usize,
+    pub dark: u32,
+    pub light: u32,
+    pub geometry: Geometry,
+    pub composite: Composite,
+}
+
+impl Default for QrLayer {
+    fn default() -> Self {
+        Self {
+            payload: "",
+            error_correction: ErrorCorrection::Medium,
+            modules: None,
+            quiet_zone: 4,
+            dark: 0x000000ff,
+            light: 0xffffffff,
+            geometry: Geometry {
+                width: Some(Length::Units(360.0)),
+                ..Geometry::default()
+            },
+            composite: Composite::default(),
+        }
+    }
+}
+
+impl From<&Node> for QrLayer {
+    fn from(value: &Node) -> Self {
+        let mut res = Self {
+            payload: value.text(),
+            ..Self::default()
+        };
+        for attribute in value.attributes() {
+            let text = attribute.value_text();
+            match attribute.name() {
+                "error_correction" => {
+                    res.error_correction = ErrorCorrection::from(text).unwrap_or(res.error_correction)
+                }
+                "quiet_zone" => res.quiet_zone = text.parse().unwrap_or(res.quiet_zone).min(MAX_QUIET_ZONE),
+                "dark" => res.dark = parse_color(text).unwrap_or(res.dark),
+                "light" => res.light = parse_color(text).unwrap_or(res.light),
+                "z" | "opacity" | "visible" => res.composite.parse(attribute),
+                _ => res.geometry.parse(attribute),
+            }
+        }
+        res.modules = QrModules::encode(res.payload, res.error_correction);
+        res
+    }
+}
+
+impl QrLayer {
+    /// Modules along each side, counting the quiet zone.
+    pub fn side(&self) -> usize {
+        self.modules.as_ref().map_or(0, |modules| modules.size) + 2 * self.quiet_zone
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::qr::*;
+
+    #[test]
+    fn encodes_payload() {
+        let modules = QrModules::encode("https://example.com/give", ErrorCorrection::Medium).unwrap();
+        assert_eq!(modules.size, 25);
+        // Every code has a finder pattern with a dark corner top left.
+        assert!(modules.is_dark(0, 0));
+        assert!(!modules.is_dark(7, 7));
+    }
+
+    #[test]
+    fn bounds_quiet_zone() {
+        let tokens = sil::lex("[qr] quiet_zone = 18446744073709551615\nexample.com");
+        let ast = sil::parse(&tokens);
+        let sil::AstNode::Node(id) = ast.view()[0] else {
+            panic!("expected a node");
+        };
+        let qr = QrLayer::from(&ast[id]);
+        assert_eq!(qr.quiet_zone, MAX_QUIET_ZONE);
+        assert_eq!(qr.side(), 21 + 2 * MAX_QUIET_ZONE);
+    }
+
+    #[test]
+    fn fails_on_payloads_too_long() {
+        let payload = "x".repeat(4000);
+        assert_eq!(QrModules::encode(&payload, ErrorCorrection::High), None);
+    }
+}
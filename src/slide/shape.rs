@@ -0,0 +1,105 @@
+use sil::Node;
+
+use crate::{parse_color, Composite, Geometry};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShapeKind {
+    Rect,
+    /// A rectangle with rounded corners.
+    Rounded,
+    Ellipse,
+    /// Runs along the longer side of the box, through its middle.
+    Line,
+}
+
+impl ShapeKind {
+    pub fn from(s: &str) -> Option<Self> {
+        match s {
+            "rect" => Some(ShapeKind::Rect),
+            "rounded" => Some(ShapeKind::Rounded),
+            "ellipse" => Some(ShapeKind::Ellipse),
+            "line" => Some(ShapeKind::Line),
+            _ => None,
+        }
+    }
+}
+
+/// Bars, boxes and dividers drawn without image assets. Sizes are in
+/// canvas units.
+#[derive(Debug, PartialEq)]
+pub struct ShapeLayer {
+    pub kind: ShapeKind,
+    pub fill: Option<u32>,
+    pub stroke: Option<u32>,
+    pub stroke_width: f32,
+    /// Corner radius of rounded rectangles.
+    pub radius: f32,
+    pub geometry: Geometry,
+    pub composite: Composite,
+}
+
+impl Default for ShapeLayer {
+    fn default() -> Self {
+        Self {
+            kind: ShapeKind::Rect,
+            fill: None,
+            stroke: None,
+            stroke_width: 2.0,
+            radius: 16.0,
+            geometry: Geometry::default(),
+            composite: Composite::default(),
+        }
+    }
+}
+
+impl From<&Node> for ShapeLayer {
+    fn from(value: &Node) -> Self {
+        let mut res = Self::default();
+        for attribute in value.attributes() {
+            let text = attribute.value_text();
+            match attribute.name() {
+                // FIXME: Make this a garbage attribute.
+                "kind" => res.kind = ShapeKind::from(text).unwrap_or(ShapeKind::Rect),
+                "fill" => res.fill = parse_color(text),
+                "stroke" => res.stroke = parse_color(text),
+                "stroke_width" => res.stroke_width = text.parse::<f32>().unwrap_or(res.stroke_width).max(0.0),
+                "radius" => res.radius = text.parse::<f32>().unwrap_or(res.radius).max(0.0),
+                "z" | "opacity" | "visible" => res.composite.parse(attribute),
+                _ => res.geometry.parse(attribute),
+            }
+        }
+        res
+    }
+}
+
+impl ShapeLayer {
+    /// Lines take their color from the stroke, then the fill.
+    pub fn line_color(&self) -> u32 {
+        self.stroke.or(self.fill).unwrap_or(0xffffffff)
+    }
+
+    /// Height of the shape when it isn't given one. Only lines have any.
+    pub fn natural_height(&self) -> f32 {
+        match self.kind {
+            ShapeKind::Line => self.stroke_width,
+            _ => 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::shape::*;
+
+    #[test]
+    fn lines_fall_back_to_fill_color() {
+        let line = ShapeLayer {
+            kind: ShapeKind::Line,
+            fill: Some(0xff0000ff),
+            ..ShapeLayer::default()
+        };
+        assert_eq!(line.line_color(), 0xff0000ff);
+        assert_eq!(line.natural_height(), 2.0);
+        assert_eq!(ShapeLayer::default().line_color(), 0xffffffff);
+    }
+}
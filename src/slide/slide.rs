@@ -1,3 +1,17 @@
+mod builds;
+mod composite;
+mod effects;
+mod export;
+mod footer;
+mod geometry;
+mod list;
+mod presentation;
+mod qr;
+mod shape;
+mod table;
+mod timer;
+mod transition;
+
 use std::ops::Index;
 
 use sil::AstNode;
@@ -5,15 +19,38 @@ use sil::Garbage;
 use sil::Node;
 use sil::AST;
 use sil::clean_up_for_attribute_key;
+use sil::notes;
+use sil::Section;
+
+pub use crate::builds::*;
+pub use crate::composite::*;
+pub use crate::effects::*;
+pub use crate::export::*;
+pub use crate::footer::*;
+pub use crate::geometry::*;
+pub use crate::list::*;
+pub use crate::presentation::*;
+pub use crate::qr::*;
+pub use crate::shape::*;
+pub use crate::table::*;
+pub use crate::timer::*;
+pub use crate::transition::*;
 
 #[derive(Default)]
 pub struct Layers(Vec<Layer>);
 
 impl From<&AST> for Layers {
     fn from(ast: &AST) -> Self {
+        Layers::from_blocks(ast, ast.view())
+    }
+}
+
+impl Layers {
+    fn from_blocks(ast: &AST, blocks: &[AstNode]) -> Self {
         let mut layers = Vec::new();
-        for block in ast.view() {
+        for block in blocks {
             match block {
+                &AstNode::Node(id) if ast[id].is_hidden() => {}
                 &AstNode::Node(id) => {
                     layers.push(Layer::from(&ast[id]));
                 }
@@ -27,6 +64,102 @@ impl From<&AST> for Layers {
     }
 }
 
+/// One slide of a deck, with the settings of the `[slide]` block that
+/// started it.
+#[derive(Default)]
+pub struct Slide {
+    pub layers: Layers,
+    pub transition: Transition,
+    pub build: Option<Build>,
+    pub build_effect: BuildEffect,
+    /// The bodies of the slide's `[notes]` blocks.
+    pub notes: Vec<&'static str>,
+    /// Set on the first slide of a section, like a verse.
+    pub section: Option<Section>,
+    /// How long the slide shows before moving on by itself, in seconds.
+    pub duration: Option<f64>,
+}
+
+impl Slide {
+    fn from_blocks(ast: &AST, settings: Option<&Node>, blocks: &[AstNode]) -> Self {
+        let mut res = Self {
+            layers: Layers::from_blocks(ast, blocks),
+            notes: notes(ast, blocks),
+            ..Self::default()
+        };
+        for attribute in settings.map_or(&[][..], Node::attributes) {
+            let text = attribute.value_text();
+            match attribute.name() {
+                "transition" => res.transition.kind = TransitionKind::from(text),
+                "transition_duration" => res.transition.duration = parse_transition_duration(text),
+                "transition_easing" => res.transition.easing = Easing::from(text),
+                "build" => res.build = Build::from(text),
+                "build_effect" => res.build_effect = BuildEffect::from(text).unwrap_or_default(),
+                "section" => res.section = Section::from(text),
+                "duration" => res.duration = parse_duration(text).filter(|duration| *duration > 0.0),
+                _ => {
+                    // FIXME: Add garbage attribute
+                }
+            }
+        }
+        res
+    }
+}
+
+/// A song or presentation. Every `[slide]` block starts a new slide; blocks
+/// before the first one make up a slide of their own.
+#[derive(Default)]
+pub struct Deck(Vec<Slide>);
+
+impl From<&AST> for Deck {
+    fn from(ast: &AST) -> Self {
+        let blocks = ast.view();
+        let slide_node = |block: &AstNode| match block {
+            &AstNode::Node(id) if ast[id].kind == "slide" => Some(&ast[id]),
+            _ => None,
+        };
+        let is_slide = |block: &AstNode| slide_node(block).is_some();
+        let is_hidden_block = |block: &AstNode| matches!(block, &AstNode::Node(id) if ast[id].is_hidden());
+
+        let mut slides = Vec::new();
+        let first = blocks.iter().position(is_slide).unwrap_or(blocks.len());
+        // `[meta]`, `[presentation]` and `[notes]` blocks at the top don't
+        // make a slide of their own. Notes there go with the first slide.
+        let mut leading_notes = Vec::new();
+        if first == blocks.len() || !blocks[..first].iter().all(is_hidden_block) {
+            slides.push(Slide::from_blocks(ast, None, &blocks[..first]));
+        } else {
+            leading_notes = notes(ast, &blocks[..first]);
+        }
+        let mut start = first;
+        while start < blocks.len() {
+            let end = blocks[start + 1..]
+                .iter()
+                .position(is_slide)
+                .map_or(blocks.len(), |offset| start + 1 + offset);
+            slides.push(Slide::from_blocks(ast, slide_node(&blocks[start]), &blocks[start + 1..end]));
+            start = end;
+        }
+        if let Some(slide) = slides.first_mut() {
+            slide.notes.splice(0..0, leading_notes);
+        }
+        Deck(slides)
+    }
+}
+
+impl Index<usize> for Deck {
+    type Output = Slide;
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl Deck {
+    pub fn view(&self) -> &[Slide] {
+        self.0.as_slice()
+    }
+}
+
 impl Index<usize> for Layers {
     type Output = Layer;
     fn index(&self, index: usize) -> &Self::Output {
@@ -38,9 +171,31 @@ impl Layers {
     pub fn view(&self) -> &[Layer] {
         self.0.as_slice()
     }
+
+    pub fn background(&self) -> Option<&BackgroundLayer> {
+        self.0.iter().rev().find_map(|layer| match layer {
+            Layer::Background(background) if background.composite.visible => Some(background),
+            _ => None,
+        })
+    }
 }
 
-#[derive(Clone, Copy)]
+/// Parses `#rrggbb` or `#rrggbbaa` into `0xrrggbbaa`.
+pub fn parse_color(s: &str) -> Option<u32> {
+    let hex = s.strip_prefix('#')?;
+    // `from_str_radix` takes a leading sign too.
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let value = u32::from_str_radix(hex, 16).ok()?;
+    match hex.len() {
+        6 => Some(value << 8 | 0xff),
+        8 => Some(value),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Align {
     Left,
     Center,
@@ -58,19 +213,73 @@ impl Align {
     }
 }
 
+// Font sizes are in canvas units, so this is relative to the project's canvas.
+pub(crate) const MAX_FONT_SIZE: f64 = 400.0;
+
+/// Reads a size in canvas units, like a font size or padding, kept within
+/// `min` and `MAX_FONT_SIZE`. NaN and infinities don't count.
+pub(crate) fn parse_size(text: &str, min: f32) -> Option<f32> {
+    let size = text.parse::<f32>().ok().filter(|size| size.is_finite())?;
+    Some(size.clamp(min, MAX_FONT_SIZE as f32))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FontSize {
+    Fixed(f64),
+    /// The largest size within the bounds at which the text fits its box.
+    Auto { min: f64, max: f64 },
+}
+
+impl FontSize {
+    fn from(text: &str, fallback: f64) -> Self {
+        if text == "auto" {
+            return FontSize::Auto {
+                min: 16.0,
+                max: 200.0,
+            };
+        }
+        let size = text.parse().ok().filter(|size: &f64| size.is_finite()).unwrap_or(fallback);
+        FontSize::Fixed(size.clamp(1.0, MAX_FONT_SIZE)) // FIXME: Make this a garbage attribute.
+    }
+
+    fn bounded(self, min: Option<f64>, max: Option<f64>) -> Self {
+        match self {
+            FontSize::Fixed(_) => self,
+            FontSize::Auto { min: default_min, max: default_max } => {
+                // `clamp` lets NaN through, which would never fit.
+                let min = min.filter(|min| min.is_finite()).unwrap_or(default_min).clamp(1.0, MAX_FONT_SIZE);
+                let max = match max {
+                    Some(max) if !max.is_finite() => min,
+                    max => max.unwrap_or(default_max).clamp(min, MAX_FONT_SIZE),
+                };
+                FontSize::Auto { min, max }
+            }
+        }
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub struct TextLayer {
     pub text: &'static str,
-    pub font_size: f64,
+    pub font_size: FontSize,
     pub font_weight: FontWeight,
+    pub geometry: Geometry,
+    pub effects: TextEffects,
+    /// Auto sized layers in the same group share the smallest fitting size.
+    pub fit_group: Option<&'static str>,
+    pub composite: Composite,
 }
 
 impl Default for TextLayer {
     fn default() -> Self {
         Self {
             text: "",
-            font_size: 16.0,
+            font_size: FontSize::Fixed(48.0),
             font_weight: FontWeight::Medium,
+            geometry: Geometry::default(),
+            effects: TextEffects::default(),
+            fit_group: None,
+            composite: Composite::default(),
         }
     }
 }
@@ -79,23 +288,22 @@ impl From<&Node> for TextLayer {
     fn from(value: &Node) -> Self {
         let mut res = Self::default();
         res.text = value.text();
+        let mut min_font_size = None;
+        let mut max_font_size = None;
         for attribute in value.attributes() {
             match attribute.name() {
-                "font_size" => {
-                    let text = attribute.value_text();
-                    res.font_size = text.parse().unwrap_or(40.0); // FIXME: Make this a garbage attribute.
-                    if res.font_size == 0.0 {
-                        res.font_size = 1.0;
-                    }
-                    if res.font_size > 100.0 {
-                        res.font_size = 100.0
-                    }
-                }
-                _ => {
-                    // FIXME: Add garbage attribute
+                "font_size" => res.font_size = FontSize::from(attribute.value_text(), 40.0),
+                "min_font_size" => min_font_size = attribute.value_text().parse().ok(),
+                "max_font_size" => max_font_size = attribute.value_text().parse().ok(),
+                "fit_group" => res.fit_group = Some(attribute.value_text()),
+                "outline" | "shadow" | "backdrop" | "backdrop_padding" | "backdrop_radius" => {
+                    res.effects.parse(attribute)
                 }
+                "z" | "opacity" | "visible" => res.composite.parse(attribute),
+                _ => res.geometry.parse(attribute),
             }
         }
+        res.font_size = res.font_size.bounded(min_font_size, max_font_size);
         return res;
     }
 }
@@ -103,18 +311,27 @@ impl From<&Node> for TextLayer {
 #[derive(Debug, PartialEq)]
 pub struct TitleLayer {
     pub text: &'static str,
-    pub font_size: f64,
+    pub font_size: FontSize,
     pub font_weight: FontWeight,
     pub color: u32,
+    pub geometry: Geometry,
+    pub effects: TextEffects,
+    /// Auto sized layers in the same group share the smallest fitting size.
+    pub fit_group: Option<&'static str>,
+    pub composite: Composite,
 }
 
 impl Default for TitleLayer {
     fn default() -> Self {
         Self {
             text: "",
-            font_size: 24.0,
+            font_size: FontSize::Fixed(72.0),
             font_weight: FontWeight::Bold,
             color: 0x00000000,
+            geometry: Geometry::default(),
+            effects: TextEffects::default(),
+            fit_group: None,
+            composite: Composite::default(),
         }
     }
 }
@@ -123,24 +340,139 @@ impl From<&Node> for TitleLayer {
     fn from(value: &Node) -> Self {
         let mut res = Self::default();
         res.text = value.text();
+        let mut min_font_size = None;
+        let mut max_font_size = None;
         for attribute in value.attributes() {
             match attribute.name() {
-                "font_size" => {
-                    let text = attribute.value_text();
-                    res.font_size = text.parse().unwrap_or(16.0); // FIXME: Make this a garbage attribute.
-                    if res.font_size == 0.0 {
-                        res.font_size = 1.0;
-                    }
-                    if res.font_size > 100.0 {
-                        res.font_size = 100.0
-                    }
+                "font_size" => res.font_size = FontSize::from(attribute.value_text(), 16.0),
+                "min_font_size" => min_font_size = attribute.value_text().parse().ok(),
+                "max_font_size" => max_font_size = attribute.value_text().parse().ok(),
+                "fit_group" => res.fit_group = Some(attribute.value_text()),
+                "outline" | "shadow" | "backdrop" | "backdrop_padding" | "backdrop_radius" => {
+                    res.effects.parse(attribute)
                 }
+                "z" | "opacity" | "visible" => res.composite.parse(attribute),
+                _ => res.geometry.parse(attribute),
+            }
+        }
+        res.font_size = res.font_size.bounded(min_font_size, max_font_size);
+        return res;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Fit {
+    Contain,
+    Cover,
+    Fill,
+    None,
+}
+
+impl Fit {
+    pub fn from(s: &str) -> Option<Self> {
+        match s {
+            "contain" => Some(Fit::Contain),
+            "cover" => Some(Fit::Cover),
+            "fill" => Some(Fit::Fill),
+            "none" => Some(Fit::None),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ImageLayer {
+    pub src: &'static str,
+    pub fit: Fit,
+    pub geometry: Geometry,
+    pub composite: Composite,
+}
+
+impl Default for ImageLayer {
+    fn default() -> Self {
+        Self {
+            src: "",
+            fit: Fit::Contain,
+            geometry: Geometry::default(),
+            composite: Composite::default(),
+        }
+    }
+}
+
+impl From<&Node> for ImageLayer {
+    fn from(value: &Node) -> Self {
+        let mut res = Self::default();
+        for attribute in value.attributes() {
+            let text = attribute.value_text();
+            match attribute.name() {
+                "src" => res.src = text,
+                "fit" => res.fit = Fit::from(text).unwrap_or(Fit::Contain), // FIXME: Make this a garbage attribute.
+                "z" | "opacity" | "visible" => res.composite.parse(attribute),
+                _ => res.geometry.parse(attribute),
+            }
+        }
+        res
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Background {
+    Color(u32),
+    /// `angle` is in degrees, where 0 runs left to right and 90 top to bottom.
+    LinearGradient { from: u32, to: u32, angle: f32 },
+    RadialGradient { from: u32, to: u32 },
+    Image { src: &'static str, fit: Fit },
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BackgroundLayer {
+    pub background: Background,
+    pub composite: Composite,
+}
+
+impl Default for BackgroundLayer {
+    fn default() -> Self {
+        Self {
+            background: Background::Color(0x000000ff),
+            composite: Composite::default(),
+        }
+    }
+}
+
+impl From<&Node> for BackgroundLayer {
+    fn from(value: &Node) -> Self {
+        let mut res = Self::default();
+        let mut color = None;
+        let mut gradient = None;
+        let mut from = 0x000000ff;
+        let mut to = 0xffffffff;
+        let mut angle = 90.0;
+        let mut src = None;
+        let mut fit = Fit::Cover;
+        for attribute in value.attributes() {
+            let text = attribute.value_text();
+            match attribute.name() {
+                "color" => color = parse_color(text),
+                "gradient" => gradient = Some(text),
+                "from" => from = parse_color(text).unwrap_or(from),
+                "to" => to = parse_color(text).unwrap_or(to),
+                "angle" => angle = text.parse().unwrap_or(angle),
+                "src" => src = Some(text),
+                "fit" => fit = Fit::from(text).unwrap_or(fit),
+                "z" | "opacity" | "visible" => res.composite.parse(attribute),
                 _ => {
                     // FIXME: Add garbage attribute
                 }
             }
         }
-        return res;
+        res.background = match (src, gradient, color) {
+            (Some(src), _, _) => Background::Image { src, fit },
+            (None, Some("linear"), _) => Background::LinearGradient { from, to, angle },
+            (None, Some("radial"), _) => Background::RadialGradient { from, to },
+            (None, _, Some(color)) => Background::Color(color),
+            (None, _, None) => res.background,
+        };
+        res
     }
 }
 
@@ -148,6 +480,13 @@ impl From<&Node> for TitleLayer {
 pub enum Layer {
     Text(TextLayer),
     Title(TitleLayer),
+    Image(ImageLayer),
+    List(ListLayer),
+    Table(TableLayer),
+    Shape(ShapeLayer),
+    Qr(QrLayer),
+    Timer(TimerLayer),
+    Background(BackgroundLayer),
     GarbageNode(Node),
     Garbage(Garbage),
 }
@@ -157,11 +496,37 @@ impl From<&Node> for Layer {
         match node.kind {
             "text" => Layer::Text(TextLayer::from(node)),
             "title" => Layer::Title(TitleLayer::from(node)),
+            "image" => Layer::Image(ImageLayer::from(node)),
+            "list" => Layer::List(ListLayer::from(node)),
+            "table" => Layer::Table(TableLayer::from(node)),
+            "shape" => Layer::Shape(ShapeLayer::from(node)),
+            "qr" => Layer::Qr(QrLayer::from(node)),
+            "countdown" => Layer::Timer(TimerLayer::from(TimerKind::Countdown(Countdown::For(300.0)), node)),
+            "clock" => Layer::Timer(TimerLayer::from(TimerKind::Clock, node)),
+            "stopwatch" => Layer::Timer(TimerLayer::from(TimerKind::Stopwatch, node)),
+            "background" => Layer::Background(BackgroundLayer::from(node)),
             _ => Layer::GarbageNode(*node),
         }
     }
 }
 
+impl Layer {
+    pub fn composite(&self) -> Composite {
+        match self {
+            Layer::Text(layer) => layer.composite,
+            Layer::Title(layer) => layer.composite,
+            Layer::Image(layer) => layer.composite,
+            Layer::List(layer) => layer.composite,
+            Layer::Table(layer) => layer.composite,
+            Layer::Shape(layer) => layer.composite,
+            Layer::Qr(layer) => layer.composite,
+            Layer::Timer(layer) => layer.composite,
+            Layer::Background(layer) => layer.composite,
+            Layer::GarbageNode(_) | Layer::Garbage(_) => Composite::default(),
+        }
+    }
+}
+
 impl From<&Garbage> for Layer {
     fn from(garbage: &Garbage) -> Self {
         Self::Garbage(*garbage)
@@ -204,9 +569,13 @@ mod tests {
             layers[0],
             Layer::Title(TitleLayer {
                 text: "Foobar",
-                font_size: 24.0,
+                font_size: FontSize::Fixed(72.0),
                 font_weight: FontWeight::Bold,
                 color: 0x00000000,
+                geometry: Geometry::default(),
+                effects: TextEffects::default(),
+                fit_group: None,
+                composite: Composite::default(),
             })
         );
     }
@@ -230,10 +599,296 @@ mod tests {
             layers[0],
             Layer::Title(TitleLayer {
                 text: "Foobar",
-                font_size: 11.0,
+                font_size: FontSize::Fixed(11.0),
                 font_weight: FontWeight::Bold,
                 color: 0x00000000,
+                geometry: Geometry::default(),
+                effects: TextEffects::default(),
+                fit_group: None,
+                composite: Composite::default(),
+            })
+        );
+    }
+
+    #[test]
+    fn can_parse_image_node() {
+        let mut ast = AST::new();
+
+        let node_slot = ast.reserve_slot();
+        ast[node_slot] = ast.push_node(Node {
+            kind: "image",
+            attributes: &[
+                Attribute {
+                    name: "src",
+                    value: &[Token::Quoted("\"media/cross.png\"")],
+                },
+                Attribute {
+                    name: "fit",
+                    value: &[Token::Quoted("\"cover\"")],
+                },
+                Attribute {
+                    name: "opacity",
+                    value: &[Token::Number("0.5")],
+                },
+            ],
+            body: &[],
+        });
+
+        let layers = Layers::from(&ast);
+        assert_eq!(
+            layers[0],
+            Layer::Image(ImageLayer {
+                src: "media/cross.png",
+                fit: Fit::Cover,
+                composite: Composite {
+                    opacity: 0.5,
+                    ..Composite::default()
+                },
+                ..ImageLayer::default()
+            })
+        );
+    }
+
+    #[test]
+    fn can_parse_colors() {
+        assert_eq!(parse_color("#ff8000"), Some(0xff8000ff));
+        assert_eq!(parse_color("#ff800080"), Some(0xff800080));
+        assert_eq!(parse_color("ff8000"), None);
+        assert_eq!(parse_color("#ff80"), None);
+        assert_eq!(parse_color("#+ff80000"), None);
+        assert_eq!(parse_color("#+ff800"), None);
+    }
+
+    #[test]
+    fn can_parse_gradient_background() {
+        let mut ast = AST::new();
+
+        let node_slot = ast.reserve_slot();
+        ast[node_slot] = ast.push_node(Node {
+            kind: "background",
+            attributes: &[
+                Attribute {
+                    name: "gradient",
+                    value: &[Token::Quoted("\"linear\"")],
+                },
+                Attribute {
+                    name: "from",
+                    value: &[Token::Quoted("\"#102030\"")],
+                },
+                Attribute {
+                    name: "to",
+                    value: &[Token::Quoted("\"#405060\"")],
+                },
+            ],
+            body: &[],
+        });
+
+        let layers = Layers::from(&ast);
+        assert_eq!(
+            layers.background(),
+            Some(&BackgroundLayer {
+                background: Background::LinearGradient {
+                    from: 0x102030ff,
+                    to: 0x405060ff,
+                    angle: 90.0,
+                },
+                composite: Composite::default(),
+            })
+        );
+    }
+
+    #[test]
+    fn can_parse_geometry() {
+        let mut ast = AST::new();
+
+        let node_slot = ast.reserve_slot();
+        ast[node_slot] = ast.push_node(Node {
+            kind: "text",
+            attributes: &[
+                Attribute {
+                    name: "x",
+                    value: &[Token::Quoted("\"50%\"")],
+                },
+                Attribute {
+                    name: "width",
+                    value: &[Token::Number("800")],
+                },
+                Attribute {
+                    name: "anchor",
+                    value: &[Token::Quoted("\"top\"")],
+                },
+            ],
+            body: &[Token::Text("Foobar")],
+        });
+
+        let layers = Layers::from(&ast);
+        assert_eq!(
+            layers[0],
+            Layer::Text(TextLayer {
+                text: "Foobar",
+                geometry: Geometry {
+                    x: Some(Length::Percent(50.0)),
+                    width: Some(Length::Units(800.0)),
+                    anchor: Anchor::Top,
+                    ..Geometry::default()
+                },
+                ..TextLayer::default()
             })
         );
     }
+
+    #[test]
+    fn can_parse_auto_font_size() {
+        let mut ast = AST::new();
+
+        let node_slot = ast.reserve_slot();
+        ast[node_slot] = ast.push_node(Node {
+            kind: "text",
+            attributes: &[
+                Attribute {
+                    name: "max_font_size",
+                    value: &[Token::Number("120")],
+                },
+                Attribute {
+                    name: "font_size",
+                    value: &[Token::Quoted("\"auto\"")],
+                },
+                Attribute {
+                    name: "fit_group",
+                    value: &[Token::Quoted("\"verses\"")],
+                },
+            ],
+            body: &[Token::Text("Foobar")],
+        });
+
+        let layers = Layers::from(&ast);
+        assert_eq!(
+            layers[0],
+            Layer::Text(TextLayer {
+                text: "Foobar",
+                font_size: FontSize::Auto {
+                    min: 16.0,
+                    max: 120.0
+                },
+                fit_group: Some("verses"),
+                ..TextLayer::default()
+            })
+        );
+    }
+
+    #[test]
+    fn ignores_nan_font_sizes() {
+        assert_eq!(FontSize::from("NaN", 32.0), FontSize::Fixed(32.0));
+        assert_eq!(
+            FontSize::from("auto", 32.0).bounded(Some(f64::NAN), Some(f64::NAN)),
+            FontSize::Auto { min: 16.0, max: 16.0 }
+        );
+    }
+
+    #[test]
+    fn splits_deck_into_slides() {
+        let mut ast = AST::new();
+        for kind in ["title", "slide", "text", "text", "slide", "slide", "text"] {
+            let node_slot = ast.reserve_slot();
+            ast[node_slot] = ast.push_node(Node {
+                kind,
+                attributes: &[],
+                body: &[],
+            });
+        }
+
+        let deck = Deck::from(&ast);
+        let lengths: Vec<usize> = deck.view().iter().map(|slide| slide.layers.view().len()).collect();
+        assert_eq!(lengths, [1, 2, 0, 1]);
+    }
+
+    #[test]
+    fn skips_meta_blocks() {
+        let mut ast = AST::new();
+        for kind in ["meta", "slide", "text", "meta"] {
+            let node_slot = ast.reserve_slot();
+            ast[node_slot] = ast.push_node(Node {
+                kind,
+                attributes: &[],
+                body: &[],
+            });
+        }
+
+        let deck = Deck::from(&ast);
+        let lengths: Vec<usize> = deck.view().iter().map(|slide| slide.layers.view().len()).collect();
+        assert_eq!(lengths, [1]);
+    }
+
+    #[test]
+    fn attaches_notes_to_slides() {
+        let tokens = lex("[notes]\nWelcome everyone\n\
+            [slide]\n[text]\nAmazing grace\n\
+            [slide]\n[text]\nHow sweet the sound\n[notes]\nPastor prays here");
+        let deck = Deck::from(&parse(&tokens));
+        assert_eq!(deck.view().len(), 2);
+        assert_eq!(deck[0].notes, ["Welcome everyone"]);
+        assert_eq!(deck[1].notes, ["Pastor prays here"]);
+        assert_eq!(deck[1].layers.view().len(), 1);
+    }
+
+    #[test]
+    fn sections_count_slides_like_decks() {
+        let tokens = lex("[notes]\nSlow\n[text]\nIntro\n[slide] section = \"verse 1\"\n[slide]\n\
+            [slide] section = \"chorus\"");
+        let ast = parse(&tokens);
+        let deck = Deck::from(&ast);
+        let from_deck: Vec<(usize, Section)> = deck
+            .view()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, slide)| Some((index, slide.section?)))
+            .collect();
+        assert_eq!(SectionIndex::from_ast(&ast).view(), from_deck);
+    }
+
+    #[test]
+    fn reads_presentation_settings() {
+        let tokens = lex("[presentation] loop = \"yes\" duration = \"8s\"\n\
+            [slide] duration = \"1m\"\n[text]\nWelcome\n\
+            [slide]\n[text]\nCoffee after the service");
+        let ast = parse(&tokens);
+        let presentation = Presentation::from_ast(&ast);
+        assert!(presentation.looping);
+        assert_eq!(presentation.duration, Some(8.0));
+        let deck = Deck::from(&ast);
+        assert_eq!(deck.view().len(), 2);
+        assert_eq!(deck[0].duration, Some(60.0));
+        assert_eq!(deck[1].duration, None);
+    }
+
+    #[test]
+    fn reads_transition_from_slide_block() {
+        let mut ast = AST::new();
+
+        let node_slot = ast.reserve_slot();
+        ast[node_slot] = ast.push_node(Node {
+            kind: "slide",
+            attributes: &[
+                Attribute {
+                    name: "transition",
+                    value: &[Token::Quoted("\"crossfade\"")],
+                },
+                Attribute {
+                    name: "transition_duration",
+                    value: &[Token::Number("1.5")],
+                },
+            ],
+            body: &[],
+        });
+
+        let deck = Deck::from(&ast);
+        assert_eq!(
+            deck[0].transition,
+            Transition {
+                kind: Some(TransitionKind::Crossfade),
+                duration: Some(1.5),
+                easing: None,
+            }
+        );
+    }
 }
@@ -0,0 +1,203 @@
+use sil::{Attribute, Node};
+
+use crate::{parse_bool, parse_color, parse_size, Align, Composite, Geometry};
+
+/// How tables look. Unset fields fall back to the theme's `[table]` block.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct TableStyle {
+    pub font_size: Option<f32>,
+    pub color: Option<u32>,
+    pub header_color: Option<u32>,
+    pub header_background: Option<u32>,
+    pub border_color: Option<u32>,
+    /// Space around the text of every cell, in canvas units.
+    pub padding: Option<f32>,
+}
+
+impl TableStyle {
+    pub fn parse(&mut self, attribute: &Attribute) {
+        let text = attribute.value_text();
+        match attribute.name() {
+            "font_size" => self.font_size = parse_size(text, 1.0),
+            "color" => self.color = parse_color(text),
+            "header_color" => self.header_color = parse_color(text),
+            "header_background" => self.header_background = parse_color(text),
+            "border_color" => self.border_color = parse_color(text),
+            "padding" => self.padding = parse_size(text, 0.0),
+            _ => {
+                // FIXME: Add garbage attribute
+            }
+        }
+    }
+
+    pub fn or(self, fallback: TableStyle) -> TableStyle {
+        TableStyle {
+            font_size: self.font_size.or(fallback.font_size),
+            color: self.color.or(fallback.color),
+            header_color: self.header_color.or(fallback.header_color),
+            header_background: self.header_background.or(fallback.header_background),
+            border_color: self.border_color.or(fallback.border_color),
+            padding: self.padding.or(fallback.padding),
+        }
+    }
+
+    pub fn font_size(&self) -> f32 {
+        self.font_size.unwrap_or(32.0)
+    }
+
+    pub fn header_background(&self) -> u32 {
+        self.header_background.unwrap_or(0xffffff20)
+    }
+
+    pub fn border_color(&self) -> u32 {
+        self.border_color.unwrap_or(0xffffff40)
+    }
+
+    pub fn padding(&self) -> f32 {
+        self.padding.unwrap_or(12.0)
+    }
+}
+
+/// Rows from a body with one row per line, its cells separated by pipes
+/// or commas. A markdown style `---|:-:` line after the header sets the
+/// column alignment.
+#[derive(Debug, PartialEq)]
+pub struct TableLayer {
+    pub rows: Vec<Vec<&'static str>>,
+    /// Whether the first row is a header.
+    pub header: bool,
+    /// Alignment by column. Columns without one are left aligned.
+    pub align: Vec<Align>,
+    pub style: TableStyle,
+    pub geometry: Geometry,
+    pub composite: Composite,
+}
+
+impl Default for TableLayer {
+    fn default() -> Self {
+        Self {
+            rows: Vec::new(),
+            header: true,
+            align: Vec::new(),
+            style: TableStyle::default(),
+            geometry: Geometry::default(),
+            composite: Composite::default(),
+        }
+    }
+}
+
+impl From<&Node> for TableLayer {
+    fn from(value: &Node) -> Self {
+        let (rows, align) = parse_rows(value.text());
+        let mut res = Self {
+            rows,
+            align,
+            ..Self::default()
+        };
+        for attribute in value.attributes() {
+            let text = attribute.value_text();
+            match attribute.name() {
+                "header" => res.header = parse_bool(text).unwrap_or(res.header),
+                "align" => {
+                    res.align = text.split_whitespace().map(|s| Align::from(s).unwrap_or(Align::Left)).collect()
+                }
+                "font_size" | "color" | "header_color" | "header_background" | "border_color" | "padding" => {
+                    res.style.parse(attribute)
+                }
+                "z" | "opacity" | "visible" => res.composite.parse(attribute),
+                _ => res.geometry.parse(attribute),
+            }
+        }
+        res
+    }
+}
+
+impl TableLayer {
+    pub fn column_count(&self) -> usize {
+        self.rows.iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    pub fn align(&self, column: usize) -> Align {
+        self.align.get(column).copied().unwrap_or(Align::Left)
+    }
+}
+
+fn parse_rows(text: &'static str) -> (Vec<Vec<&'static str>>, Vec<Align>) {
+    let pipes = text.contains('|');
+    let mut rows = Vec::new();
+    let mut align = Vec::new();
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let cells = if pipes { split_pipes(line) } else { split_csv(line) };
+        match separator_alignment(&cells) {
+            Some(alignment) if pipes => align = alignment,
+            _ => rows.push(cells),
+        }
+    }
+    (rows, align)
+}
+
+fn split_pipes(line: &'static str) -> Vec<&'static str> {
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = line.strip_suffix('|').unwrap_or(line);
+    line.split('|').map(str::trim).collect()
+}
+
+fn split_csv(line: &'static str) -> Vec<&'static str> {
+    let mut cells = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    for (index, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ',' if !quoted => {
+                cells.push(unquote(&line[start..index]));
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    cells.push(unquote(&line[start..]));
+    cells
+}
+
+fn unquote(cell: &'static str) -> &'static str {
+    let cell = cell.trim();
+    cell.strip_prefix('"').and_then(|cell| cell.strip_suffix('"')).unwrap_or(cell)
+}
+
+/// Reads the alignment out of rows like `---|:-:|--:`.
+fn separator_alignment(cells: &[&str]) -> Option<Vec<Align>> {
+    cells
+        .iter()
+        .map(|cell| {
+            let dashes = cell.trim_start_matches(':').trim_end_matches(':');
+            if dashes.is_empty() || dashes.chars().any(|c| c != '-') {
+                return None;
+            }
+            Some(match (cell.starts_with(':'), cell.ends_with(':')) {
+                (true, true) => Align::Center,
+                (false, true) => Align::Right,
+                _ => Align::Left,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::table::*;
+
+    #[test]
+    fn can_parse_pipe_tables() {
+        let (rows, align) = parse_rows("| Song | Key |\n|---|:-:|\n| Amazing Grace | G |");
+        assert_eq!(rows, [vec!["Song", "Key"], vec!["Amazing Grace", "G"]]);
+        assert_eq!(align, [Align::Left, Align::Center]);
+    }
+
+    #[test]
+    fn can_parse_csv_tables() {
+        let (rows, align) = parse_rows("Time, Event\n10:00, \"Coffee, tea\"");
+        assert_eq!(rows, [vec!["Time", "Event"], vec!["10:00", "Coffee, tea"]]);
+        assert!(align.is_empty());
+    }
+}
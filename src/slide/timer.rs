@@ -0,0 +1,256 @@
+use sil::Node;
+
+use crate::{parse_size, Composite, Geometry, Layer, Slide, TextEffects};
+
+const DAY: f64 = 24.0 * 60.0 * 60.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Countdown {
+    /// Counts down to a time of day, in seconds after midnight.
+    Until(f64),
+    /// Counts down this many seconds from when the slide is shown.
+    For(f64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimerKind {
+    Countdown(Countdown),
+    /// The time of day.
+    Clock,
+    /// Counts up from when the slide is shown.
+    Stopwatch,
+}
+
+/// What a countdown does once it runs out.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum AtZero {
+    /// Stays on screen showing zero.
+    #[default]
+    Hold,
+    Hide,
+    /// Moves on to the next slide.
+    Advance,
+}
+
+impl AtZero {
+    pub fn from(s: &str) -> Option<Self> {
+        match s {
+            "hold" => Some(AtZero::Hold),
+            "hide" => Some(AtZero::Hide),
+            "advance" => Some(AtZero::Advance),
+            _ => None,
+        }
+    }
+}
+
+/// Countdowns, clocks and stopwatches. Their text is worked out every
+/// frame from the time instead of the body.
+#[derive(Debug, PartialEq)]
+pub struct TimerLayer {
+    pub kind: TimerKind,
+    /// A strftime format for clocks, like `%H:%M`.
+    pub format: &'static str,
+    pub at_zero: AtZero,
+    pub font_size: f32,
+    pub geometry: Geometry,
+    pub effects: TextEffects,
+    pub composite: Composite,
+}
+
+impl TimerLayer {
+    pub fn from(kind: TimerKind, value: &Node) -> Self {
+        let mut res = Self {
+            kind,
+            format: "%H:%M",
+            at_zero: AtZero::default(),
+            font_size: 96.0,
+            geometry: Geometry::default(),
+            effects: TextEffects::default(),
+            composite: Composite::default(),
+        };
+        for attribute in value.attributes() {
+            let text = attribute.value_text();
+            match attribute.name() {
+                // FIXME: Make these garbage attributes.
+                "to" => {
+                    if let Some(time) = parse_time_of_day(text) {
+                        res.kind = TimerKind::Countdown(Countdown::Until(time));
+                    }
+                }
+                "duration" => {
+                    if let Some(duration) = parse_duration(text) {
+                        res.kind = TimerKind::Countdown(Countdown::For(duration));
+                    }
+                }
+                "format" => res.format = text,
+                "at_zero" => res.at_zero = AtZero::from(text).unwrap_or(res.at_zero),
+                "font_size" => res.font_size = parse_size(text, 1.0).unwrap_or(res.font_size),
+                "outline" | "shadow" | "backdrop" | "backdrop_padding" | "backdrop_radius" => {
+                    res.effects.parse(attribute)
+                }
+                "z" | "opacity" | "visible" => res.composite.parse(attribute),
+                _ => res.geometry.parse(attribute),
+            }
+        }
+        res
+    }
+
+    /// Seconds left on a countdown, given the seconds since the slide was
+    /// shown and the time of day in seconds after midnight. A time of day
+    /// more than twelve hours back is taken to mean tomorrow.
+    pub fn remaining(&self, elapsed: f64, time_of_day: f64) -> Option<f64> {
+        let remaining = match self.kind {
+            TimerKind::Countdown(Countdown::For(duration)) => duration - elapsed,
+            TimerKind::Countdown(Countdown::Until(time)) => {
+                let remaining = time - time_of_day;
+                if remaining < -DAY / 2.0 {
+                    remaining + DAY
+                } else {
+                    remaining
+                }
+            }
+            _ => return None,
+        };
+        Some(remaining.max(0.0))
+    }
+
+    /// The text of countdowns and stopwatches, or `None` for hidden
+    /// countdowns and clocks.
+    pub fn text(&self, elapsed: f64, time_of_day: f64) -> Option<String> {
+        match self.kind {
+            TimerKind::Countdown(_) => {
+                let remaining = self.remaining(elapsed, time_of_day)?;
+                if remaining == 0.0 && self.at_zero == AtZero::Hide {
+                    return None;
+                }
+                // Round up, so zero only shows once time is up.
+                Some(format_duration(remaining.ceil()))
+            }
+            TimerKind::Stopwatch => Some(format_duration(elapsed.max(0.0).floor())),
+            TimerKind::Clock => None,
+        }
+    }
+}
+
+impl Slide {
+    /// Layers showing the time, which need drawing every frame.
+    pub fn timers(&self) -> impl Iterator<Item = &TimerLayer> {
+        self.layers.view().iter().filter_map(|layer| match layer {
+            Layer::Timer(timer) => Some(timer),
+            _ => None,
+        })
+    }
+}
+
+/// Formats whole seconds as `mm:ss`, or `h:mm:ss` from an hour up.
+pub fn format_duration(seconds: f64) -> String {
+    let seconds = seconds as u64;
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes:02}:{seconds:02}")
+    }
+}
+
+/// Reads `hh:mm` or `hh:mm:ss` as seconds after midnight.
+fn parse_time_of_day(s: &str) -> Option<f64> {
+    let parts: Vec<u32> = s.split(':').map(|part| part.trim().parse().ok()).collect::<Option<_>>()?;
+    let (hours, minutes, seconds) = match parts[..] {
+        [hours, minutes] => (hours, minutes, 0),
+        [hours, minutes, seconds] => (hours, minutes, seconds),
+        _ => return None,
+    };
+    if hours >= 24 || minutes >= 60 || seconds >= 60 {
+        return None;
+    }
+    Some((hours * 3600 + minutes * 60 + seconds) as f64)
+}
+
+/// Reads durations like `90s`, `5m`, `1h30m` or `4:30`, in seconds. Bare
+/// numbers are seconds.
+pub(crate) fn parse_duration(s: &str) -> Option<f64> {
+    let s = s.trim();
+    if s.contains(':') {
+        return s
+            .split(':')
+            .try_fold(0.0, |total, part| Some(total * 60.0 + part.trim().parse::<u32>().ok()? as f64));
+    }
+    if let Ok(seconds) = s.parse::<f64>() {
+        return Some(seconds).filter(|seconds| *seconds >= 0.0);
+    }
+    let mut total = 0.0;
+    let mut number = String::new();
+    for c in s.chars() {
+        let unit = match c {
+            'h' => 3600.0,
+            'm' => 60.0,
+            's' => 1.0,
+            _ => {
+                number.push(c);
+                continue;
+            }
+        };
+        total += number.trim().parse::<f64>().ok()? * unit;
+        number.clear();
+    }
+    if !number.trim().is_empty() {
+        return None;
+    }
+    Some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::timer::*;
+    use crate::MAX_FONT_SIZE;
+
+    #[test]
+    fn can_parse_durations() {
+        assert_eq!(parse_duration("5m"), Some(300.0));
+        assert_eq!(parse_duration("1h30m"), Some(5400.0));
+        assert_eq!(parse_duration("90s"), Some(90.0));
+        assert_eq!(parse_duration("4:30"), Some(270.0));
+        assert_eq!(parse_duration("45"), Some(45.0));
+        assert_eq!(parse_duration("5 minutes"), None);
+        assert_eq!(parse_time_of_day("10:30"), Some(37800.0));
+        assert_eq!(parse_time_of_day("24:00"), None);
+    }
+
+    #[test]
+    fn ignores_nan_font_sizes() {
+        let tokens = sil::lex("[stopwatch] font_size = \"NaN\"\n[stopwatch] font_size = 1000");
+        let ast = sil::parse(&tokens);
+        let font_sizes: Vec<f32> = ast
+            .view()
+            .iter()
+            .filter_map(|block| match block {
+                &sil::AstNode::Node(id) => Some(TimerLayer::from(TimerKind::Stopwatch, &ast[id]).font_size),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(font_sizes, [96.0, MAX_FONT_SIZE as f32]);
+    }
+
+    #[test]
+    fn countdowns_stop_at_zero() {
+        let mut countdown = TimerLayer {
+            kind: TimerKind::Countdown(Countdown::For(300.0)),
+            format: "",
+            at_zero: AtZero::Hold,
+            font_size: 96.0,
+            geometry: Geometry::default(),
+            effects: TextEffects::default(),
+            composite: Composite::default(),
+        };
+        assert_eq!(countdown.text(0.5, 0.0).as_deref(), Some("05:00"));
+        assert_eq!(countdown.text(299.5, 0.0).as_deref(), Some("00:01"));
+        assert_eq!(countdown.text(400.0, 0.0).as_deref(), Some("00:00"));
+        countdown.at_zero = AtZero::Hide;
+        assert_eq!(countdown.text(400.0, 0.0), None);
+
+        // Counting down to 00:05 from just before midnight.
+        countdown.kind = TimerKind::Countdown(Countdown::Until(300.0));
+        assert_eq!(countdown.remaining(0.0, DAY - 60.0), Some(360.0));
+    }
+}
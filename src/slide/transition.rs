@@ -0,0 +1,157 @@
+use sil::Node;
+
+// In seconds. Longer transitions would only hold up the next slide.
+const MAX_DURATION: f32 = 10.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransitionKind {
+    Cut,
+    Crossfade,
+    FadeThroughBlack,
+    /// The incoming slide slides in over the outgoing one.
+    Slide,
+    /// The incoming slide pushes the outgoing one out.
+    Push,
+    /// Crossfades everything but the background.
+    Dissolve,
+}
+
+impl TransitionKind {
+    pub fn from(s: &str) -> Option<Self> {
+        match s {
+            "cut" => Some(TransitionKind::Cut),
+            "crossfade" => Some(TransitionKind::Crossfade),
+            "fade_through_black" => Some(TransitionKind::FadeThroughBlack),
+            "slide" => Some(TransitionKind::Slide),
+            "push" => Some(TransitionKind::Push),
+            "dissolve" => Some(TransitionKind::Dissolve),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing {
+    pub fn from(s: &str) -> Option<Self> {
+        match s {
+            "linear" => Some(Easing::Linear),
+            "ease_in" => Some(Easing::EaseIn),
+            "ease_out" => Some(Easing::EaseOut),
+            "ease_in_out" => Some(Easing::EaseInOut),
+            _ => None,
+        }
+    }
+
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// How a slide is brought on screen. Unset fields fall back to the theme,
+/// then to the project, then to a cut.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Transition {
+    pub kind: Option<TransitionKind>,
+    /// In seconds.
+    pub duration: Option<f32>,
+    pub easing: Option<Easing>,
+}
+
+impl From<&Node> for Transition {
+    fn from(value: &Node) -> Self {
+        let mut res = Self::default();
+        for attribute in value.attributes() {
+            let text = attribute.value_text();
+            match attribute.name() {
+                "kind" => res.kind = TransitionKind::from(text),
+                "duration" => res.duration = parse_transition_duration(text),
+                "easing" => res.easing = Easing::from(text),
+                _ => {
+                    // FIXME: Add garbage attribute
+                }
+            }
+        }
+        res
+    }
+}
+
+/// Reads a transition's duration in seconds. Durations that never end,
+/// like `inf`, are dropped.
+pub(crate) fn parse_transition_duration(text: &str) -> Option<f32> {
+    let duration = text.parse::<f32>().ok().filter(|duration| duration.is_finite())?;
+    Some(duration.clamp(0.0, MAX_DURATION))
+}
+
+impl Transition {
+    pub fn or(self, fallback: Transition) -> Transition {
+        Transition {
+            kind: self.kind.or(fallback.kind),
+            duration: self.duration.or(fallback.duration),
+            easing: self.easing.or(fallback.easing),
+        }
+    }
+
+    pub fn kind(&self) -> TransitionKind {
+        self.kind.unwrap_or(TransitionKind::Cut)
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.duration.unwrap_or(0.5).max(0.0)
+    }
+
+    pub fn easing(&self) -> Easing {
+        self.easing.unwrap_or(Easing::EaseInOut)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::transition::*;
+
+    #[test]
+    fn bounds_durations() {
+        assert_eq!(parse_transition_duration("0.8"), Some(0.8));
+        assert_eq!(parse_transition_duration("inf"), None);
+        assert_eq!(parse_transition_duration("NaN"), None);
+        assert_eq!(parse_transition_duration("1e20"), Some(MAX_DURATION));
+        assert_eq!(parse_transition_duration("-1"), Some(0.0));
+    }
+
+    #[test]
+    fn falls_back_field_by_field() {
+        let slide = Transition {
+            kind: Some(TransitionKind::Push),
+            ..Transition::default()
+        };
+        let theme = Transition {
+            kind: Some(TransitionKind::Crossfade),
+            duration: Some(1.5),
+            easing: None,
+        };
+        let transition = slide.or(theme);
+        assert_eq!(transition.kind(), TransitionKind::Push);
+        assert_eq!(transition.duration(), 1.5);
+        assert_eq!(transition.easing(), Easing::EaseInOut);
+    }
+
+    #[test]
+    fn easing_keeps_end_points() {
+        for easing in [Easing::Linear, Easing::EaseIn, Easing::EaseOut, Easing::EaseInOut] {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+        }
+    }
+}
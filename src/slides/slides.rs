@@ -1,13 +1,51 @@
-use egui::{Align, Layout, Widget};
+use egui::{Align, Color32, Layout, RichText, Widget};
+use sil::{Section, SectionKind};
 
 pub struct Slides {
     file_names: Vec<String>,
     selected_slide: usize,
+    /// Sections of what's live: the slide each starts on, its kind and
+    /// short label.
+    sections: Vec<(usize, SectionKind, String)>,
+    /// Where the section the output is in starts.
+    current_section: Option<usize>,
+    /// Where a clicked section starts.
+    clicked_section: Option<usize>,
 }
 
 impl Slides {
     pub fn update_if_needed(&mut self) {
     }
+
+    pub fn select_next(&mut self) {
+        self.selected_slide = (self.selected_slide + 1).min(self.file_names.len().saturating_sub(1));
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected_slide = self.selected_slide.saturating_sub(1);
+    }
+
+    /// Shows the sections of what's live under the selected item, marking
+    /// the one slide `index` is in.
+    pub fn set_sections(&mut self, sections: &[(usize, Section)], index: usize) {
+        let changed = sections.len() != self.sections.len()
+            || sections
+                .iter()
+                .zip(&self.sections)
+                .any(|((start, section), (known, _, label))| start != known || section.short_label() != *label);
+        if changed {
+            self.sections = sections
+                .iter()
+                .map(|(start, section)| (*start, section.kind, section.short_label()))
+                .collect();
+        }
+        self.current_section = sections.iter().rev().map(|(start, _)| *start).find(|start| *start <= index);
+    }
+
+    /// The slide a clicked section starts on, once.
+    pub fn take_clicked_section(&mut self) -> Option<usize> {
+        self.clicked_section.take()
+    }
 }
 
 impl Default for Slides {
@@ -15,10 +53,25 @@ impl Default for Slides {
         Self {
             file_names: ["Foo".to_owned(), "Bar".to_owned(), "Baz".to_owned()].to_vec(),
             selected_slide: 0,
+            sections: Vec::new(),
+            current_section: None,
+            clicked_section: None,
         }
     }
 }
 
+/// Tells sections apart at a glance.
+fn section_color(kind: SectionKind) -> Color32 {
+    match kind {
+        SectionKind::Verse => Color32::from_rgb(70, 130, 220),
+        SectionKind::Chorus => Color32::from_rgb(220, 60, 90),
+        SectionKind::PreChorus => Color32::from_rgb(230, 150, 40),
+        SectionKind::Bridge => Color32::from_rgb(150, 80, 210),
+        SectionKind::Ending => Color32::from_rgb(60, 170, 90),
+        SectionKind::Other => Color32::GRAY,
+    }
+}
+
 impl Widget for &mut Slides {
     fn ui(self, ui: &mut egui::Ui) -> egui::Response {
         ui.with_layout(Layout::top_down_justified(Align::Min), |ui| {
@@ -29,6 +82,20 @@ impl Widget for &mut Slides {
                 {
                     self.selected_slide = i;
                 }
+                if self.selected_slide != i || self.sections.is_empty() {
+                    continue;
+                }
+                ui.horizontal_wrapped(|ui| {
+                    for (start, kind, label) in &self.sections {
+                        let text = RichText::new(label.as_str())
+                            .color(Color32::WHITE)
+                            .background_color(section_color(*kind));
+                        let current = self.current_section == Some(*start);
+                        if ui.selectable_label(current, text).clicked() {
+                            self.clicked_section = Some(*start);
+                        }
+                    }
+                });
             }
         })
         .response